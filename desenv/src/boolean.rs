@@ -0,0 +1,48 @@
+use std::fmt::{Debug, Display, Formatter};
+
+const TRUTHY: [&str; 5] = ["true", "1", "yes", "y", "on"];
+const FALSY: [&str; 5] = ["false", "0", "no", "n", "off"];
+
+/// The error returned when a value is not one of the accepted boolean spellings.
+pub struct BoolError(String);
+
+impl Debug for BoolError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Display for BoolError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid boolean `{}`, expected one of `{}` or `{}`",
+            self.0,
+            TRUTHY.join("`, `"),
+            FALSY.join("`, `")
+        )
+    }
+}
+
+impl std::error::Error for BoolError {}
+
+/// Parse a boolean leniently.
+///
+/// Accepted values, case-insensitive and ignoring surrounding whitespaces, are
+/// `true`, `1`, `yes`, `y`, `on` and `false`, `0`, `no`, `n`, `off`. It's used for `bool` fields unless
+/// the `strict_bool` modifier is set.
+///
+/// # Errors
+///
+/// Will return `Err` if `value` is not one of the accepted spellings.
+pub fn parse_bool(value: &str) -> Result<bool, BoolError> {
+    let lowercase: String = value.trim().to_lowercase();
+
+    if TRUTHY.contains(&lowercase.as_str()) {
+        Ok(true)
+    } else if FALSY.contains(&lowercase.as_str()) {
+        Ok(false)
+    } else {
+        Err(BoolError(value.to_string()))
+    }
+}
@@ -0,0 +1,240 @@
+//! Parser for `.env` files. The supported syntax is:
+//!
+//! - `KEY=value` pairs, one per line. Whitespaces around the key and the value are ignored.
+//! - `export KEY=value`, where the `export` prefix is ignored.
+//! - Comments: lines starting with `#` and, for unquoted values, everything after a `#` preceded by a
+//!   whitespace.
+//! - Single quoted values (`KEY='value'`), taken literally.
+//! - Double quoted values (`KEY="value"`), where `\n`, `\r`, `\t`, `\\`, `\"` and `\$` escapes are
+//!   expanded.
+//!
+//! Quoted values may span multiple lines. If a key is defined more than once the last value wins.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter, Result};
+use std::iter::Peekable;
+use std::path::Path;
+use std::str::Chars;
+
+const EXPORT: &str = "export";
+
+/// All possible errors returned reading a `.env` file. The errors are:
+///
+/// - `Io`: the file could not be read.
+/// - `Syntax`: the file content is not well-formed. Carries the (1-based) line where the error was found.
+pub enum DotenvError {
+    Io(std::io::Error),
+    Syntax { line: usize, message: String },
+}
+
+impl Debug for DotenvError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Display for DotenvError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Io(error) => write!(f, "Cannot read dotenv file: {error}"),
+            Self::Syntax { line, message } => write!(f, "Invalid dotenv file at line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for DotenvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Syntax { .. } => None,
+        }
+    }
+}
+
+/// Read and parse the `.env` file at `path`.
+///
+/// # Errors
+///
+/// Will return `Err` if the file cannot be read or if its content is not well-formed.
+pub fn from_path<P>(path: P) -> std::result::Result<HashMap<String, String>, DotenvError>
+where
+    P: AsRef<Path>,
+{
+    let content: String = std::fs::read_to_string(path).map_err(DotenvError::Io)?;
+    parse(&content)
+}
+
+/// Parse the given `.env` file content.
+///
+/// # Errors
+///
+/// Will return `Err` if the content is not well-formed.
+pub fn parse(content: &str) -> std::result::Result<HashMap<String, String>, DotenvError> {
+    let mut parser: Parser = Parser { chars: content.chars().peekable(), line: 1 };
+    let mut vars: HashMap<String, String> = HashMap::new();
+
+    while let Some((key, value)) = parser.next_var()? {
+        vars.insert(key, value);
+    }
+
+    Ok(vars)
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl Parser<'_> {
+    fn next_var(&mut self) -> std::result::Result<Option<(String, String)>, DotenvError> {
+        loop {
+            self.skip_whitespaces();
+            match self.chars.peek() {
+                None => return Ok(None),
+                Some('\n') => self.bump(),
+                Some('#') => self.skip_line(),
+                Some(_) => break,
+            }
+        }
+
+        let mut key: String = self.key()?;
+        self.skip_whitespaces();
+
+        if key == EXPORT && self.chars.peek() != Some(&'=') {
+            key = self.key()?;
+            self.skip_whitespaces();
+        }
+
+        if self.chars.peek() != Some(&'=') {
+            return Err(self.error(format!("expected `=` after `{key}`")));
+        }
+        self.bump();
+        self.skip_whitespaces();
+
+        let value: String = match self.chars.peek() {
+            Some('\'') => self.single_quoted()?,
+            Some('"') => self.double_quoted()?,
+            _ => return Ok(Some((key, self.unquoted()))),
+        };
+
+        self.skip_whitespaces();
+        match self.chars.peek() {
+            None | Some('\n') => Ok(Some((key, value))),
+            Some('#') => {
+                self.skip_line();
+                Ok(Some((key, value)))
+            }
+            Some(&c) => Err(self.error(format!("unexpected `{c}` after quoted value of `{key}`"))),
+        }
+    }
+
+    fn key(&mut self) -> std::result::Result<String, DotenvError> {
+        let mut key: String = String::new();
+
+        while let Some(&c) = self.chars.peek() {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '.' {
+                key.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+
+        if key.is_empty() || key.starts_with(|c: char| c.is_ascii_digit()) {
+            Err(self.error(format!("invalid key `{key}`")))
+        } else {
+            Ok(key)
+        }
+    }
+
+    fn unquoted(&mut self) -> String {
+        let mut value: String = String::new();
+
+        while let Some(&c) = self.chars.peek() {
+            if c == '\n' || (c == '#' && (value.is_empty() || value.ends_with(char::is_whitespace))) {
+                break;
+            }
+            value.push(c);
+            self.bump();
+        }
+
+        self.skip_line();
+        value.trim_end().to_string()
+    }
+
+    fn single_quoted(&mut self) -> std::result::Result<String, DotenvError> {
+        let start: usize = self.line;
+        let mut value: String = String::new();
+        self.bump();
+
+        loop {
+            match self.chars.peek() {
+                None => return Err(Self::unterminated(start)),
+                Some('\'') => break,
+                Some(&c) => value.push(c),
+            }
+            self.bump();
+        }
+
+        self.bump();
+        Ok(value)
+    }
+
+    fn double_quoted(&mut self) -> std::result::Result<String, DotenvError> {
+        let start: usize = self.line;
+        let mut value: String = String::new();
+        self.bump();
+
+        loop {
+            match self.chars.peek() {
+                None => return Err(Self::unterminated(start)),
+                Some('"') => break,
+                Some('\\') => {
+                    self.bump();
+                    match self.chars.peek() {
+                        None => return Err(Self::unterminated(start)),
+                        Some('n') => value.push('\n'),
+                        Some('r') => value.push('\r'),
+                        Some('t') => value.push('\t'),
+                        Some(&c) if c == '\\' || c == '"' || c == '$' => value.push(c),
+                        Some(&c) => {
+                            value.push('\\');
+                            value.push(c);
+                        }
+                    }
+                }
+                Some(&c) => value.push(c),
+            }
+            self.bump();
+        }
+
+        self.bump();
+        Ok(value)
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while matches!(self.chars.peek(), Some(&c) if c != '\n' && c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn skip_line(&mut self) {
+        while matches!(self.chars.peek(), Some(&c) if c != '\n') {
+            self.bump();
+        }
+    }
+
+    fn bump(&mut self) {
+        if self.chars.next() == Some('\n') {
+            self.line += 1;
+        }
+    }
+
+    fn unterminated(start: usize) -> DotenvError {
+        DotenvError::Syntax { line: start, message: "unterminated quoted value".to_string() }
+    }
+
+    const fn error(&self, message: String) -> DotenvError {
+        DotenvError::Syntax { line: self.line, message }
+    }
+}
@@ -1,38 +1,340 @@
 use std::fmt::{Debug, Display, Formatter, Result};
+use std::str::FromStr;
+
+use crate::dotenv::DotenvError;
 
 /// All possible errors returned by this library after the [`desenv::load`] call. The errors are:
 ///
 /// - `MissingVar`: the environment variable could not be found
 /// - `NotUnicodeVar`: the environment variable exists but is not UTF-8 encoded.
-/// - `ParseFromStr`: the environment variable contains a value that cannot be parsed to original value.
+/// - `ParseFromStr`: the environment variable contains a value that cannot be parsed to original value. See
+///   [`ParseError`] for the details it carries.
+/// - `Validation`: the value of a variable was parsed but violates one of the rules set with the `validate`
+///   or `validate_with` modifiers. See [`ValidationError`] for the details it carries.
 /// - `Custom`: custom error defined by the user of this library. Is mainly used to handle the errors
-///             of a custom deserializable type (mixing in the [`std::str::FromStr`] trait).
+///   of a custom deserializable type (mixing in the [`std::str::FromStr`] trait).
+/// - `UnreadableFile`: the variable is missing and the file at the path held by the `<VAR>_FILE` variable
+///   (carried along with the I/O error) cannot be read.
+/// - `Dotenv`: the `.env` file given to [`desenv::load_with_dotenv`] cannot be read or is not well-formed.
+/// - `Multiple`: more than one of the errors above occurred while loading the configuration. Every field
+///   is loaded anyway so that all the errors are reported at once. Never contains another `Multiple`.
 pub enum Error {
     MissingVar(String),
     NotUnicodeVar(String),
-    ParseFromStr(String),
+    ParseFromStr(ParseError),
+    Validation(ValidationError),
     Custom(String),
+    UnreadableFile(String, std::io::Error),
+    Dotenv(DotenvError),
+    Multiple(Vec<Self>),
+}
+
+impl Error {
+    /// Merge the given errors in a single one, flattening nested `Multiple` errors. If exactly one
+    /// error is given it is returned as is.
+    /// DO NOT USE THIS FUNCTION! It's meant to be used by the code generated by the `Desenv` macro.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn merge(errors: Vec<Option<Self>>) -> Self {
+        let mut errors: Vec<Self> = errors
+            .into_iter()
+            .flatten()
+            .flat_map(|error| match error {
+                Self::Multiple(errors) => errors,
+                error => vec![error],
+            })
+            .collect();
+
+        if errors.len() == 1 {
+            errors.remove(0)
+        } else {
+            Self::Multiple(errors)
+        }
+    }
+
+    /// Remove every raw variable value carried by this error, in order to safely log it.
+    #[must_use]
+    pub fn redact(self) -> Self {
+        match self {
+            Self::ParseFromStr(error) => Self::ParseFromStr(error.redact()),
+            Self::Multiple(errors) => Self::Multiple(errors.into_iter().map(Self::redact).collect()),
+            error => error,
+        }
+    }
+
+    /// Redact the error and hide the parse errors too, since their messages may contain the raw value. It's
+    /// applied to the errors of secret fields.
+    /// DO NOT USE THIS FUNCTION! It's meant to be used by the code generated by the `Desenv` macro.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn conceal(self) -> Self {
+        match self {
+            Self::ParseFromStr(error) => Self::ParseFromStr(error.conceal()),
+            Self::Multiple(errors) => Self::Multiple(errors.into_iter().map(Self::conceal).collect()),
+            error => error,
+        }
+    }
 }
 
 impl Debug for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "{}", self)
+        write!(f, "{self}")
     }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
-            Self::MissingVar(error) => write!(f, "Missing env var `{}`", error),
-            Self::NotUnicodeVar(error) => write!(f, "Env var is not unicode `{}`", error),
-            Self::ParseFromStr(error) => write!(f, "Cannot parse env var: {}", error),
-            Self::Custom(error) => write!(f, "{}", error),
+            Self::MissingVar(error) => write!(f, "Missing env var `{error}`"),
+            Self::NotUnicodeVar(error) => write!(f, "Env var is not unicode `{error}`"),
+            Self::ParseFromStr(error) => write!(f, "{error}"),
+            Self::Validation(error) => write!(f, "{error}"),
+            Self::Custom(error) => write!(f, "{error}"),
+            Self::UnreadableFile(var, error) => write!(f, "Cannot read file from env var `{var}`: {error}"),
+            Self::Dotenv(error) => write!(f, "{error}"),
+            Self::Multiple(errors) => {
+                write!(f, "Found {} errors:", errors.len())?;
+                errors.iter().try_for_each(|error| write!(f, "\n- {error}"))
+            }
         }
     }
 }
 
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        None
+        match self {
+            Self::ParseFromStr(error) => Some(error.source.as_ref()),
+            Self::UnreadableFile(_, error) => Some(error),
+            Self::Dotenv(error) => error.source(),
+            _ => None,
+        }
+    }
+}
+
+/// The error occurred parsing the value of a variable to the type of the field it's loaded into.
+pub struct ParseError {
+    var: String,
+    path: String,
+    value: Option<String>,
+    source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl ParseError {
+    /// Create a new error for the `value` of the `var` variable that failed to be parsed to the type of the
+    /// field at `path` because of `source`.
+    pub fn new<E>(var: &str, path: &str, value: &str, source: E) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        Self {
+            var: var.to_string(),
+            path: path.to_string(),
+            value: Some(value.to_string()),
+            source: source.into(),
+        }
+    }
+
+    /// Parse `value`, read from the `var` variable, to the type of the field at `path`.
+    /// DO NOT USE THIS FUNCTION! It's meant to be used by the code generated by the `Desenv` macro.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `value` cannot be parsed to `T`.
+    #[doc(hidden)]
+    pub fn parse<T>(value: &str, var: &str, path: &str) -> std::result::Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        value
+            .parse()
+            .map_err(|error| Error::ParseFromStr(Self::new(var, path, value, error)))
+    }
+
+    /// Parse `value`, read from the `var` variable, to the type of the field at `path` using the custom
+    /// `parser` set with the `parse_with` modifier.
+    /// DO NOT USE THIS FUNCTION! It's meant to be used by the code generated by the `Desenv` macro.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `parser` fails.
+    #[doc(hidden)]
+    pub fn parse_with<T, E, F>(value: &str, var: &str, path: &str, parser: F) -> std::result::Result<T, Error>
+    where
+        F: FnOnce(&str) -> std::result::Result<T, E>,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        parser(value).map_err(|error| Error::ParseFromStr(Self::new(var, path, value, error)))
+    }
+
+    /// The name of the variable holding the value that failed to parse.
+    #[must_use]
+    pub fn var(&self) -> &str {
+        &self.var
+    }
+
+    /// The path of the field the value was loaded into, starting from the loaded struct (ex.
+    /// `Config.db.port`).
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The value that failed to parse, or `None` if it has been redacted.
+    #[must_use]
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// Remove the value that failed to parse, in order to safely log the error.
+    #[must_use]
+    pub fn redact(self) -> Self {
+        Self { value: None, ..self }
+    }
+
+    fn conceal(self) -> Self {
+        Self { value: None, source: Box::new(Concealed), ..self }
+    }
+}
+
+impl Debug for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("ParseError")
+            .field("var", &self.var)
+            .field("path", &self.path)
+            .field("value", &self.value)
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "Cannot parse env var `{}` into `{}`: {}",
+            self.var, self.path, self.source
+        )
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_ref())
     }
 }
+
+/// The error occurred validating the value of a variable after it has been parsed.
+pub struct ValidationError {
+    var: String,
+    path: String,
+    rule: String,
+}
+
+impl ValidationError {
+    /// Create a new error for the value of the `var` variable, loaded into the field at `path`, violating
+    /// `rule` (ex. `must not be empty`).
+    #[must_use]
+    pub fn new(var: &str, path: &str, rule: &str) -> Self {
+        Self { var: var.to_string(), path: path.to_string(), rule: rule.to_string() }
+    }
+
+    /// The name of the variable holding the invalid value.
+    #[must_use]
+    pub fn var(&self) -> &str {
+        &self.var
+    }
+
+    /// The path of the field (ex. `Config.port`).
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The description of the violated rule.
+    #[must_use]
+    pub fn rule(&self) -> &str {
+        &self.rule
+    }
+}
+
+impl Debug for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("ValidationError")
+            .field("var", &self.var)
+            .field("path", &self.path)
+            .field("rule", &self.rule)
+            .finish()
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Invalid env var `{}` for `{}`: {}", self.var, self.path, self.rule)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+// Replaces the source of the parse errors of secret fields.
+struct Concealed;
+
+impl Debug for Concealed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Display for Concealed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", crate::secret::REDACTED)
+    }
+}
+
+impl std::error::Error for Concealed {}
+
+/// The error returned parsing a value that does not match any variant of an enum deriving `DesenvValue`.
+pub struct VariantError {
+    value: String,
+    expected: &'static [&'static str],
+}
+
+impl VariantError {
+    /// Create a new error for the given `value`, listing the `expected` ones.
+    #[must_use]
+    pub fn new(value: &str, expected: &'static [&'static str]) -> Self {
+        Self { value: value.to_string(), expected }
+    }
+
+    /// The value that does not match any variant.
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// All the accepted values.
+    #[must_use]
+    pub const fn expected(&self) -> &'static [&'static str] {
+        self.expected
+    }
+}
+
+impl Debug for VariantError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Display for VariantError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let expected: Vec<String> = self.expected.iter().map(|value| format!("`{value}`")).collect();
+        write!(
+            f,
+            "unknown variant `{}`, expected one of {}",
+            self.value,
+            expected.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for VariantError {}
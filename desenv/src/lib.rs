@@ -1,10 +1,36 @@
 //! Desenv-rs is a library used to deserialize the environment variables into a given struct deriving
 //! `Desenv` macro.
 
-pub use desenv_macros::Desenv;
-pub use error::Error;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::path::Path;
 
+pub use boolean::{parse_bool, BoolError};
+pub use desenv_macros::{Desenv, DesenvValue};
+pub use error::{Error, ParseError, ValidationError, VariantError};
+#[doc(hidden)]
+pub use nested::{check_nested_option, check_nested_vec, nested_option, nested_vec};
+#[doc(hidden)]
+pub use os_str::split_os_str;
+pub use os_str::FromOsStr;
+pub use secret::Secret;
+#[doc(hidden)]
+pub use source::{fallback_var, var_alias, var_or_file};
+pub use source::{Env, OnWarning, Or, Source};
+pub use warning::Warning;
+
+mod boolean;
+pub mod dotenv;
 mod error;
+mod nested;
+mod os_str;
+mod secret;
+mod source;
+pub mod units;
+#[doc(hidden)]
+pub mod validate;
+mod warning;
 
 /// Load all the environment variables into a given `Desenv` struct.
 ///
@@ -20,11 +46,139 @@ pub fn load<T>() -> Result<T, Error>
 where
     T: Desenv,
 {
-    T::_load(None)
+    load_from(&Env)
+}
+
+/// Load all the variables from the given [`Source`] into a given `Desenv` struct.
+///
+/// # Errors
+///
+/// Will return `Err` in the same cases as [`desenv::load`], looking up the variables in `source`
+/// instead of the process environment.
+pub fn load_from<T, S>(source: &S) -> Result<T, Error>
+where
+    T: Desenv,
+    S: Source,
+{
+    T::_load(None, None, source)
+}
+
+/// Load all the environment variables into a given `Desenv` struct, returning the warnings raised while
+/// loading it along with the struct.
+///
+/// # Errors
+///
+/// Will return `Err` in the same cases as [`desenv::load`].
+pub fn load_with_warnings<T>() -> Result<(T, Vec<Warning>), Error>
+where
+    T: Desenv,
+{
+    load_from_with_warnings(&Env)
+}
+
+/// Load all the variables from the given [`Source`] into a given `Desenv` struct, returning the warnings
+/// raised while loading it along with the struct.
+///
+/// # Errors
+///
+/// Will return `Err` in the same cases as [`desenv::load_from`].
+pub fn load_from_with_warnings<T, S>(source: &S) -> Result<(T, Vec<Warning>), Error>
+where
+    T: Desenv,
+    S: Source,
+{
+    let warnings: RefCell<Vec<Warning>> = RefCell::new(vec![]);
+    let value: T = load_from(&source.on_warning(|warning: &Warning| warnings.borrow_mut().push(warning.clone())))?;
+    Ok((value, warnings.into_inner()))
+}
+
+/// Load all the environment variables into a given `Desenv` struct, falling back to the variables defined
+/// in the `.env` file at `path`.
+///
+/// The variables missing in the environment are read from the file. The process environment is not
+/// modified. See [`dotenv`] for the supported syntax.
+///
+/// # Errors
+///
+/// Will return `Err` if the `.env` file cannot be read or is not well-formed, and in the same cases as
+/// [`desenv::load`].
+pub fn load_with_dotenv<T, P>(path: P) -> Result<T, Error>
+where
+    T: Desenv,
+    P: AsRef<Path>,
+{
+    let dotenv: HashMap<String, String> = dotenv::from_path(path).map_err(Error::Dotenv)?;
+    load_from(&Env.or(dotenv))
+}
+
+/// Load all the variables from the given map into a given `Desenv` struct, without touching the process
+/// environment. Mostly useful to write hermetic tests.
+///
+/// # Errors
+///
+/// Will return `Err` in the same cases as [`desenv::load`], looking up the variables in `map` instead of
+/// the process environment.
+pub fn load_from_map<T, H>(map: &HashMap<String, String, H>) -> Result<T, Error>
+where
+    T: Desenv,
+    H: BuildHasher,
+{
+    load_from(map)
+}
+
+/// Load all the variables from the given key/value pairs into a given `Desenv` struct, without touching
+/// the process environment. If the same key is given more than once the last value wins.
+///
+/// # Errors
+///
+/// Will return `Err` in the same cases as [`desenv::load`], looking up the variables in `vars` instead of
+/// the process environment.
+pub fn load_from_iter<T, I, K, V>(vars: I) -> Result<T, Error>
+where
+    T: Desenv,
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<String>,
+{
+    let map: HashMap<String, String> = vars.into_iter().map(|(k, v)| (k.into(), v.into())).collect();
+    load_from_map(&map)
+}
+
+/// Check that the defaults of the given `Desenv` struct, and of the nested ones, are valid.
+///
+/// The values set with `default = "..."` (vectors and maps included) must be parsable to the field type and every
+/// default must satisfy the `validate` rules of its field. Meant to be called in a test, so that a wrong default
+/// fails in CI rather than when the variable is missing in production.
+///
+/// # Errors
+///
+/// Will return `Err` with the `ParseFromStr` and `Validation` errors of the invalid defaults.
+#[must_use = "the invalid defaults are only reported in the returned `Result`"]
+pub fn check_defaults<T>() -> Result<(), Error>
+where
+    T: Desenv,
+{
+    // Fields without a default are reported as missing: they are not what is checked here.
+    let errors: Vec<Option<Error>> = T::_check_defaults(None, None)
+        .into_iter()
+        .flat_map(|error| match error {
+            Error::Multiple(errors) => errors,
+            error => vec![error],
+        })
+        .filter(|error| matches!(error, Error::ParseFromStr(_) | Error::Validation(_)))
+        .map(Some)
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::merge(errors))
+    }
 }
 
 pub trait Desenv {
-    /// Load the configuration with the given optional `parent_prefix`.
+    /// Load the configuration with the given optional `parent_prefix` from the given `source`. The
+    /// `parent_path` is the path of the field holding this configuration, if it's nested.
     /// DO NOT USE THIS FUNCTION! Use [`desenv::load`] instead!
     ///
     /// # Errors
@@ -35,7 +189,19 @@ pub trait Desenv {
     /// - Deserialization of default value from string to resulting type fails.
     /// - Both environment variable for non-optional field and default environment variable
     ///   is missing.
-    fn _load(parent_prefix: Option<String>) -> Result<Self, Error>
+    fn _load(parent_prefix: Option<String>, parent_path: Option<String>, source: &dyn Source) -> Result<Self, Error>
     where
         Self: Sized;
+
+    /// Load every field from an empty source, so that the defaults are used, and return the errors. The
+    /// variants of tagged enums are all checked.
+    /// DO NOT USE THIS FUNCTION! Use [`desenv::check_defaults`] instead!
+    #[doc(hidden)]
+    #[must_use]
+    fn _check_defaults(_parent_prefix: Option<String>, _parent_path: Option<String>) -> Vec<Error>
+    where
+        Self: Sized,
+    {
+        vec![]
+    }
 }
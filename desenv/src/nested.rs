@@ -0,0 +1,136 @@
+use std::cell::Cell;
+use std::env::VarError;
+use std::ffi::OsString;
+use std::marker::PhantomData;
+
+use crate::{Desenv, Error, Source, Warning};
+
+/// Load the elements of a vector of nested structs, reading the element at index `i` from the variables
+/// prefixed by `<prefix><i>_`. Indices are probed starting from `0` until one without variables is found.
+/// DO NOT USE THIS FUNCTION! It's meant to be used by the code generated by the `Desenv` macro.
+///
+/// # Errors
+///
+/// Will return `Err` if any of the elements fails to load. Every failing element is reported.
+#[doc(hidden)]
+pub fn nested_vec<T>(prefix: &str, path: &str, source: &dyn Source) -> Result<Vec<T>, Error>
+where
+    T: Desenv,
+{
+    let mut values: Vec<T> = vec![];
+    let mut errors: Vec<Option<Error>> = vec![];
+
+    for index in 0.. {
+        let probe: Probe = Probe::new(source, format!("{prefix}{index}_"));
+        let result: Result<T, Error> = T::_load(Some(probe.prefix.clone()), Some(format!("{path}[{index}]")), &probe);
+
+        if !probe.found.get() {
+            break;
+        }
+
+        match result {
+            Ok(value) => values.push(value),
+            Err(error) => errors.push(Some(error)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(Error::merge(errors))
+    }
+}
+
+/// Load an optional nested struct: the result is `None` if none of its variables are found, otherwise the
+/// struct is loaded as usual.
+/// DO NOT USE THIS FUNCTION! It's meant to be used by the code generated by the `Desenv` macro.
+///
+/// # Errors
+///
+/// Will return `Err` if some of the struct variables are found but the struct fails to load (ex. when other
+/// required variables are missing).
+#[doc(hidden)]
+pub fn nested_option<T>(prefix: Option<String>, path: String, source: &dyn Source) -> Result<Option<T>, Error>
+where
+    T: Desenv,
+{
+    let probe: Probe = Probe::new(source, prefix.clone().unwrap_or_default());
+    let result: Result<T, Error> = T::_load(prefix, Some(path), &probe);
+
+    if probe.found.get() {
+        result.map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Check the defaults of the elements of a vector of nested structs, as if the first element was set. The
+/// element type is inferred from `field`, so that type aliases of vectors are supported too.
+/// DO NOT USE THIS FUNCTION! It's meant to be used by the code generated by the `Desenv` macro.
+#[doc(hidden)]
+#[must_use]
+pub fn check_nested_vec<T>(prefix: &str, path: &str, _field: PhantomData<Vec<T>>) -> Vec<Error>
+where
+    T: Desenv,
+{
+    T::_check_defaults(Some(format!("{prefix}0_")), Some(format!("{path}[0]")))
+}
+
+/// Check the defaults of an optional nested struct, as if it was set. The struct type is inferred from
+/// `field`, so that type aliases of options are supported too.
+/// DO NOT USE THIS FUNCTION! It's meant to be used by the code generated by the `Desenv` macro.
+#[doc(hidden)]
+#[must_use]
+pub fn check_nested_option<T>(prefix: Option<String>, path: String, _field: PhantomData<Option<T>>) -> Vec<Error>
+where
+    T: Desenv,
+{
+    T::_check_defaults(prefix, Some(path))
+}
+
+// A source keeping track of whether any variable under `prefix` has been found. The fallback variables (the ones
+// used as `default(env)`) are not taken into account, even if they are under the prefix.
+struct Probe<'a> {
+    source: &'a dyn Source,
+    prefix: String,
+    found: Cell<bool>,
+}
+
+impl<'a> Probe<'a> {
+    fn new(source: &'a dyn Source, prefix: String) -> Self {
+        Self { source, prefix, found: Cell::new(false) }
+    }
+
+    fn track(&self, key: &str, found: bool) {
+        if found && key.starts_with(self.prefix.as_str()) {
+            self.found.set(true);
+        }
+    }
+}
+
+impl Source for Probe<'_> {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        let value: Option<OsString> = self.source.var_os(key);
+        self.track(key, value.is_some());
+        value
+    }
+
+    fn var(&self, key: &str) -> Result<String, VarError> {
+        let value: Result<String, VarError> = self.source.var(key);
+        self.track(key, !matches!(value, Err(VarError::NotPresent)));
+        value
+    }
+
+    // Listing the names is not a lookup: the scanned maps read the matching variables afterwards, which are tracked.
+    fn var_names(&self) -> Vec<String> {
+        self.source.var_names()
+    }
+
+    fn fallback_var_os(&self, key: &str) -> Option<OsString> {
+        self.source.fallback_var_os(key)
+    }
+
+    fn warn(&self, warning: Warning) {
+        self.source.warn(warning);
+    }
+}
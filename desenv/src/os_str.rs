@@ -0,0 +1,83 @@
+use std::ffi::{OsStr, OsString};
+use std::path::PathBuf;
+
+/// Conversion from the raw value of a variable, which is not required to be valid unicode.
+///
+/// Fields whose type implements this trait are loaded without going through `FromStr`: the macro recognizes `OsString`,
+/// `PathBuf` and optional values or vectors of them, while other types need the `kind = "os_string"` field
+/// modifier.
+pub trait FromOsStr: Sized {
+    /// Build the value from the raw variable.
+    fn from_os_str(value: &OsStr) -> Self;
+
+    /// The value to use if the variable is missing, or `None` if the variable is required.
+    #[must_use]
+    fn missing() -> Option<Self> {
+        None
+    }
+}
+
+impl FromOsStr for OsString {
+    fn from_os_str(value: &OsStr) -> Self {
+        value.to_os_string()
+    }
+}
+
+impl FromOsStr for PathBuf {
+    fn from_os_str(value: &OsStr) -> Self {
+        Self::from(value)
+    }
+}
+
+impl<T> FromOsStr for Option<T>
+where
+    T: FromOsStr,
+{
+    fn from_os_str(value: &OsStr) -> Self {
+        Some(T::from_os_str(value))
+    }
+
+    fn missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+/// Vectors are split on the platform path separator (`:` on unix, `;` on windows), like the `PATH` variable.
+/// Empty entries are skipped.
+impl<T> FromOsStr for Vec<T>
+where
+    T: FromOsStr,
+{
+    fn from_os_str(value: &OsStr) -> Self {
+        std::env::split_paths(value)
+            .filter(|path| !path.as_os_str().is_empty())
+            .map(|path| T::from_os_str(path.as_os_str()))
+            .collect()
+    }
+}
+
+/// Split the raw value of a variable on `separator`, skipping empty entries. On unix the value is not
+/// required to be valid unicode if `separator` is an ASCII char.
+/// DO NOT USE THIS FUNCTION! It's meant to be used by the code generated by the `Desenv` macro.
+#[doc(hidden)]
+#[must_use]
+pub fn split_os_str(value: &OsStr, separator: char) -> Vec<OsString> {
+    #[cfg(unix)]
+    if separator.is_ascii() {
+        use std::os::unix::ffi::OsStrExt;
+
+        return value
+            .as_bytes()
+            .split(|&byte| byte == separator as u8)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| OsStr::from_bytes(entry).to_os_string())
+            .collect();
+    }
+
+    value
+        .to_string_lossy()
+        .split(separator)
+        .filter(|entry| !entry.is_empty())
+        .map(OsString::from)
+        .collect()
+}
@@ -0,0 +1,56 @@
+use std::fmt::{Debug, Display, Formatter, Result};
+use std::str::FromStr;
+
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A value that must not end up in logs: both `Debug` and `Display` print `[REDACTED]`.
+///
+/// Fields of this type are loaded as if they had the `secret` modifier, so that the errors never contain the raw value.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    /// Wrap the given value.
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns a reference to the secret value.
+    pub const fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns the secret value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> FromStr for Secret<T>
+where
+    T: FromStr,
+{
+    type Err = T::Err;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        value.parse().map(Self)
+    }
+}
+
+impl<T> Debug for Secret<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{REDACTED}")
+    }
+}
+
+impl<T> Display for Secret<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{REDACTED}")
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::{BTreeMap, HashMap};
+use std::env::VarError;
+use std::ffi::OsString;
+use std::hash::BuildHasher;
+use std::path::PathBuf;
+
+use crate::{Error, Warning};
+
+const FILE_SUFFIX: &str = "_FILE";
+
+/// A source of variables the `Desenv` structs are loaded from.
+///
+/// The library ships with [`Env`], reading the process environment, but any key/value store can be plugged in
+/// implementing this trait and calling [`desenv::load_from`].
+pub trait Source {
+    /// Returns the raw value of the variable named `key`, or `None` if it's missing.
+    fn var_os(&self, key: &str) -> Option<OsString>;
+
+    /// Returns the value of the variable named `key` as a `String`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the variable is missing or if it's not valid unicode.
+    fn var(&self, key: &str) -> Result<String, VarError> {
+        self.var_os(key).map_or(Err(VarError::NotPresent), |value| {
+            value.into_string().map_err(VarError::NotUnicode)
+        })
+    }
+
+    /// Returns the names of all the variables in the source. It's used to load map fields scanning the
+    /// variables under a prefix: sources that cannot be enumerated can keep the default implementation,
+    /// returning no names.
+    fn var_names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Returns the raw value of the fallback variable named `key`, set with `default(env = "...")`. Unlike
+    /// [`Source::var_os`], these lookups do not reveal whether an optional nested struct is set.
+    /// DO NOT USE THIS FUNCTION! It's meant to be used by the code generated by the `Desenv` macro.
+    #[doc(hidden)]
+    fn fallback_var_os(&self, key: &str) -> Option<OsString> {
+        self.var_os(key)
+    }
+
+    /// Reports a warning raised while loading the configuration from this source. The default implementation
+    /// discards it: see [`Source::on_warning`] to handle the warnings.
+    fn warn(&self, _warning: Warning) {}
+
+    /// Returns a source looking up the variables in `self` and calling `hook` on every warning raised while
+    /// loading the configuration (ex. to log them).
+    fn on_warning<F>(self, hook: F) -> OnWarning<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&Warning),
+    {
+        OnWarning { source: self, hook }
+    }
+
+    /// Returns a source looking up the variables in `self` first and then, if missing, in `fallback`.
+    fn or<S>(self, fallback: S) -> Or<Self, S>
+    where
+        Self: Sized,
+        S: Source,
+    {
+        Or { primary: self, fallback }
+    }
+}
+
+impl<S> Source for &S
+where
+    S: Source + ?Sized,
+{
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        (**self).var_os(key)
+    }
+
+    fn var(&self, key: &str) -> Result<String, VarError> {
+        (**self).var(key)
+    }
+
+    fn var_names(&self) -> Vec<String> {
+        (**self).var_names()
+    }
+
+    fn fallback_var_os(&self, key: &str) -> Option<OsString> {
+        (**self).fallback_var_os(key)
+    }
+
+    fn warn(&self, warning: Warning) {
+        (**self).warn(warning);
+    }
+}
+
+/// The process environment. This is the source used by [`desenv::load`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Env;
+
+impl Source for Env {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        std::env::var_os(key)
+    }
+
+    fn var(&self, key: &str) -> Result<String, VarError> {
+        std::env::var(key)
+    }
+
+    fn var_names(&self) -> Vec<String> {
+        std::env::vars_os()
+            .filter_map(|(key, _)| key.into_string().ok())
+            .collect()
+    }
+}
+
+/// Look up the variable named `key` in `source`. If it's missing, look up the `<key>_FILE` variable and return
+/// the content of the file at the path it holds, without the trailing newline.
+/// DO NOT USE THIS FUNCTION! It's meant to be used by the code generated by the `Desenv` macro.
+///
+/// # Errors
+///
+/// Will return `Err` if the file cannot be read.
+#[doc(hidden)]
+pub fn var_or_file(source: &dyn Source, key: &str) -> Result<Result<String, VarError>, Error> {
+    let file_key: String = format!("{key}{FILE_SUFFIX}");
+
+    match (source.var(key), source.var_os(&file_key)) {
+        (Err(VarError::NotPresent), Some(file_path)) => std::fs::read_to_string(PathBuf::from(file_path))
+            .map(|content| Ok(content.trim_end_matches(['\n', '\r']).to_string()))
+            .map_err(|error| Error::UnreadableFile(file_key, error)),
+        (result, _) => Ok(result),
+    }
+}
+
+/// Look up the fallback variable named `key`, set with `default(env = "...")`, in `source`.
+/// DO NOT USE THIS FUNCTION! It's meant to be used by the code generated by the `Desenv` macro.
+///
+/// # Errors
+///
+/// Will return `Err` if the variable is missing or if it's not valid unicode.
+#[doc(hidden)]
+pub fn fallback_var(source: &dyn Source, key: &str) -> Result<String, VarError> {
+    source.fallback_var_os(key).map_or(Err(VarError::NotPresent), |value| {
+        value.into_string().map_err(VarError::NotUnicode)
+    })
+}
+
+/// Returns the first of `var` and its `aliases` (along with whether it's deprecated) set in `source`, falling
+/// back to `var` if none is set. If `file` is set, the `<name>_FILE` variables are looked up too. A warning is
+/// raised if a deprecated alias is returned and the `path` of the field is given.
+/// DO NOT USE THIS FUNCTION! It's meant to be used by the code generated by the `Desenv` macro.
+#[doc(hidden)]
+pub fn var_alias(
+    source: &dyn Source,
+    var: String,
+    aliases: &[(String, bool)],
+    file: bool,
+    path: Option<&str>,
+) -> String {
+    let is_set = |name: &str| -> bool {
+        source.var_os(name).is_some() || (file && source.var_os(&format!("{name}{FILE_SUFFIX}")).is_some())
+    };
+
+    if is_set(&var) {
+        return var;
+    }
+
+    match aliases.iter().find(|(alias, _)| is_set(alias)) {
+        Some((alias, deprecated)) => {
+            if let (true, Some(path)) = (deprecated, path) {
+                source.warn(Warning::DeprecatedVar { var: alias.clone(), replacement: var, path: path.to_string() });
+            }
+            alias.clone()
+        }
+        None => var,
+    }
+}
+
+/// A source layering two other sources, built with [`Source::or`].
+#[derive(Clone, Debug)]
+pub struct Or<A, B> {
+    primary: A,
+    fallback: B,
+}
+
+impl<A, B> Source for Or<A, B>
+where
+    A: Source,
+    B: Source,
+{
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        self.primary.var_os(key).or_else(|| self.fallback.var_os(key))
+    }
+
+    fn var(&self, key: &str) -> Result<String, VarError> {
+        match self.primary.var(key) {
+            Err(VarError::NotPresent) => self.fallback.var(key),
+            result => result,
+        }
+    }
+
+    fn var_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.primary.var_names();
+        names.extend(self.fallback.var_names());
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    fn warn(&self, warning: Warning) {
+        self.primary.warn(warning.clone());
+        self.fallback.warn(warning);
+    }
+}
+
+/// A source reporting the warnings to a hook, built with [`Source::on_warning`].
+#[derive(Clone, Debug)]
+pub struct OnWarning<S, F> {
+    source: S,
+    hook: F,
+}
+
+impl<S, F> Source for OnWarning<S, F>
+where
+    S: Source,
+    F: Fn(&Warning),
+{
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        self.source.var_os(key)
+    }
+
+    fn var(&self, key: &str) -> Result<String, VarError> {
+        self.source.var(key)
+    }
+
+    fn var_names(&self) -> Vec<String> {
+        self.source.var_names()
+    }
+
+    fn warn(&self, warning: Warning) {
+        (self.hook)(&warning);
+        self.source.warn(warning);
+    }
+}
+
+impl<H> Source for HashMap<String, String, H>
+where
+    H: BuildHasher,
+{
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        self.get(key).map(OsString::from)
+    }
+
+    fn var(&self, key: &str) -> Result<String, VarError> {
+        self.get(key).cloned().ok_or(VarError::NotPresent)
+    }
+
+    fn var_names(&self) -> Vec<String> {
+        self.keys().cloned().collect()
+    }
+}
+
+impl Source for BTreeMap<String, String> {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        self.get(key).map(OsString::from)
+    }
+
+    fn var(&self, key: &str) -> Result<String, VarError> {
+        self.get(key).cloned().ok_or(VarError::NotPresent)
+    }
+
+    fn var_names(&self) -> Vec<String> {
+        self.keys().cloned().collect()
+    }
+}
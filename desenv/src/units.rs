@@ -0,0 +1,185 @@
+//! Parsers for human-readable durations and byte sizes.
+//!
+//! `std::time::Duration` fields (optional values, vectors and maps of them too) are parsed with [`duration`]
+//! out of the box, while byte sizes are loaded using the [`ByteSize`] type. Both functions can also be used
+//! with the `parse_with` field modifier.
+
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+const DURATION_UNITS: [(&str, u128); 8] = [
+    ("ns", 1),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ms", 1_000_000),
+    ("s", NANOS_PER_SEC),
+    ("m", 60 * NANOS_PER_SEC),
+    ("h", 60 * 60 * NANOS_PER_SEC),
+    ("d", 24 * 60 * 60 * NANOS_PER_SEC),
+];
+
+const BYTE_UNITS: [(&str, u128); 11] = [
+    ("b", 1),
+    ("kb", 1_000),
+    ("mb", 1_000_000),
+    ("gb", 1_000_000_000),
+    ("tb", 1_000_000_000_000),
+    ("pb", 1_000_000_000_000_000),
+    ("kib", 1 << 10),
+    ("mib", 1 << 20),
+    ("gib", 1 << 30),
+    ("tib", 1 << 40),
+    ("pib", 1 << 50),
+];
+
+/// The error returned when a duration or a byte size cannot be parsed.
+pub struct UnitError(String);
+
+impl Debug for UnitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Display for UnitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for UnitError {}
+
+/// Parse a duration written as a sequence of numbers followed by a unit, like `1h30m`, `250ms` or `1.5s`.
+///
+/// Supported units are `d`, `h`, `m`, `s`, `ms`, `us` (or `µs`) and `ns`. A plain `0` is accepted too.
+///
+/// # Errors
+///
+/// Will return `Err` if `value` is empty, if a number has no unit or an unknown one, or if the duration
+/// overflows.
+pub fn duration(value: &str) -> Result<Duration, UnitError> {
+    let value: &str = value.trim();
+    if value == "0" {
+        return Ok(Duration::ZERO);
+    }
+    if value.is_empty() {
+        return Err(UnitError("empty duration".to_string()));
+    }
+
+    let mut nanos: u128 = 0;
+    let mut rest: &str = value;
+
+    while !rest.is_empty() {
+        let (number, unit, remaining) = next_quantity(rest);
+        if number.is_empty() {
+            return Err(invalid("duration", value));
+        }
+
+        let unit_nanos: u128 = match DURATION_UNITS.iter().find(|(name, _)| *name == unit) {
+            Some((_, unit_nanos)) => *unit_nanos,
+            None if unit.is_empty() => return Err(UnitError(format!("missing unit in duration `{value}`"))),
+            None => return Err(UnitError(format!("unknown unit `{unit}` in duration `{value}`"))),
+        };
+        nanos = scale(number, unit_nanos)
+            .and_then(|quantity| nanos.checked_add(quantity))
+            .ok_or_else(|| invalid("duration", value))?;
+        rest = remaining;
+    }
+
+    let secs: u64 = u64::try_from(nanos / NANOS_PER_SEC).map_err(|_| invalid("duration", value))?;
+    // The remainder is always lower than a second.
+    #[allow(clippy::cast_possible_truncation)]
+    Ok(Duration::new(secs, (nanos % NANOS_PER_SEC) as u32))
+}
+
+/// Parse a size in bytes written as a number optionally followed by a unit, like `512KiB`, `10MB` or
+/// `1.5 GiB`.
+///
+/// Supported units are `B`, the decimal `KB`, `MB`, `GB`, `TB`, `PB` and the binary `KiB`, `MiB`,
+/// `GiB`, `TiB`, `PiB`, case-insensitive. A number without unit is a count of bytes.
+///
+/// # Errors
+///
+/// Will return `Err` if `value` is not a number followed by a known unit, or if the size overflows a `u64`.
+pub fn bytes(value: &str) -> Result<u64, UnitError> {
+    let trimmed: &str = value.trim();
+    let (number, unit, rest) = next_quantity(trimmed);
+
+    if number.is_empty() || !rest.is_empty() {
+        return Err(invalid("byte size", value));
+    }
+
+    let unit: String = unit.to_lowercase();
+    let unit_bytes: u128 = match unit.as_str() {
+        "" => 1,
+        unit => match BYTE_UNITS.iter().find(|(name, _)| *name == unit) {
+            Some((_, unit_bytes)) => *unit_bytes,
+            None => return Err(UnitError(format!("unknown unit `{unit}` in byte size `{value}`"))),
+        },
+    };
+
+    scale(number, unit_bytes)
+        .and_then(|bytes| u64::try_from(bytes).ok())
+        .ok_or_else(|| invalid("byte size", value))
+}
+
+/// A size in bytes parsed from a human-readable string using [`bytes`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ByteSize(pub u64);
+
+impl ByteSize {
+    /// The size in bytes.
+    #[must_use]
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = UnitError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        bytes(value).map(Self)
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(size: ByteSize) -> Self {
+        size.0
+    }
+}
+
+// Split the leading quantity of `value` in its number, its unit and the remaining string. Whitespaces between
+// the number and the unit are skipped.
+fn next_quantity(value: &str) -> (&str, &str, &str) {
+    let number_end: usize = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, rest) = value.split_at(number_end);
+    let rest: &str = rest.trim_start();
+    let unit_end: usize = rest
+        .find(|c: char| c.is_ascii_digit() || c.is_whitespace())
+        .unwrap_or(rest.len());
+    let (unit, rest) = rest.split_at(unit_end);
+
+    (number, unit, rest.trim_start())
+}
+
+// Multiply the decimal `number` by `unit`, truncating the fractional part of the result.
+fn scale(number: &str, unit: u128) -> Option<u128> {
+    let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if integer.is_empty() || fraction.contains('.') {
+        return None;
+    }
+
+    let digits: u128 = format!("{integer}{fraction}").parse().ok()?;
+    let divisor: u128 = 10u128.checked_pow(u32::try_from(fraction.len()).ok()?)?;
+    digits.checked_mul(unit).map(|value| value / divisor)
+}
+
+fn invalid(kind: &str, value: &str) -> UnitError {
+    UnitError(format!("invalid {kind} `{value}`"))
+}
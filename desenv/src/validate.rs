@@ -0,0 +1,107 @@
+//! The rules of the `validate` field modifier.
+//! DO NOT USE THIS MODULE! It's meant to be used by the code generated by the `Desenv` macro. Every rule
+//! returns the description of the violation as `Err`.
+
+use std::fmt::Display;
+
+pub fn range<T>(value: &T, min: Option<T>, max: Option<T>) -> Result<(), String>
+where
+    T: PartialOrd + Display,
+{
+    let too_low: bool = min.as_ref().map_or(false, |min| value < min);
+    let too_high: bool = max.as_ref().map_or(false, |max| value > max);
+
+    if too_low || too_high {
+        Err(bounds("must be", min, max))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn len(len: usize, min: Option<usize>, max: Option<usize>) -> Result<(), String> {
+    if min.map_or(false, |min| len < min) || max.map_or(false, |max| len > max) {
+        Err(bounds("length must be", min, max))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn non_empty(is_empty: bool) -> Result<(), String> {
+    if is_empty {
+        Err("must not be empty".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+pub fn one_of<T, U>(value: &T, allowed: &[U]) -> Result<(), String>
+where
+    T: PartialEq<U>,
+    U: Display,
+{
+    if allowed.iter().any(|allowed| value == allowed) {
+        Ok(())
+    } else {
+        let allowed: Vec<String> = allowed.iter().map(|allowed| format!("`{allowed}`")).collect();
+        Err(format!("must be one of {}", allowed.join(", ")))
+    }
+}
+
+/// The pattern of a `regex` rule, compiled the first time it is checked. The generated code keeps it in a
+/// static, so that the pattern is compiled once.
+// `OnceLock` needs Rust 1.70, like `regex-lite`: the `regex` feature does not follow the crate MSRV.
+#[cfg(feature = "regex")]
+#[allow(clippy::incompatible_msrv)]
+pub struct Regex {
+    pattern: &'static str,
+    compiled: std::sync::OnceLock<Result<regex_lite::Regex, String>>,
+}
+
+#[cfg(feature = "regex")]
+#[allow(clippy::incompatible_msrv)]
+impl Regex {
+    #[must_use]
+    pub const fn new(pattern: &'static str) -> Self {
+        Self { pattern, compiled: std::sync::OnceLock::new() }
+    }
+}
+
+#[cfg(feature = "regex")]
+#[allow(clippy::incompatible_msrv)]
+pub fn regex(value: &str, regex: &Regex) -> Result<(), String> {
+    let pattern: &str = regex.pattern;
+    let compiled: &regex_lite::Regex = regex
+        .compiled
+        .get_or_init(|| regex_lite::Regex::new(pattern).map_err(|error| format!("invalid regex `{pattern}`: {error}")))
+        .as_ref()
+        .map_err(Clone::clone)?;
+
+    if compiled.is_match(value) {
+        Ok(())
+    } else {
+        Err(format!("must match `{pattern}`"))
+    }
+}
+
+pub fn with<E>(result: Result<(), E>) -> Result<(), String>
+where
+    E: Display,
+{
+    result.map_err(|error| error.to_string())
+}
+
+pub fn with_concealed<E>(result: Result<(), E>) -> Result<(), String> {
+    result.map_err(|_error| "custom validation failed".to_string())
+}
+
+fn bounds<T>(prefix: &str, min: Option<T>, max: Option<T>) -> String
+where
+    T: Display,
+{
+    match (min, max) {
+        (Some(min), Some(max)) => format!("{prefix} between {min} and {max}"),
+        (Some(min), None) => format!("{prefix} at least {min}"),
+        (None, Some(max)) => format!("{prefix} at most {max}"),
+        (None, None) => String::new(),
+    }
+}
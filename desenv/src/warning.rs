@@ -0,0 +1,31 @@
+use std::fmt::{Display, Formatter, Result};
+
+/// All possible warnings raised while loading the configuration.
+///
+/// Unlike errors, warnings do not prevent the
+/// configuration from loading: they are collected by [`desenv::load_with_warnings`] or reported to the hook
+/// set with [`Source::on_warning`]. The warnings are:
+///
+/// - `DeprecatedVar`: the field has been loaded from `var`, set with the `deprecated_alias` modifier, while the
+///   variable to use is `replacement`. Carries the path of the field, too.
+///
+/// [`Source::on_warning`]: crate::Source::on_warning
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Warning {
+    DeprecatedVar {
+        var: String,
+        replacement: String,
+        path: String,
+    },
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::DeprecatedVar { var, replacement, path } => write!(
+                f,
+                "Deprecated env var `{var}` for `{path}`, use `{replacement}` instead"
+            ),
+        }
+    }
+}
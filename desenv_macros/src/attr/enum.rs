@@ -0,0 +1,159 @@
+use proc_macro2::{Ident, Span};
+use syn::spanned::Spanned;
+use syn::{Attribute, Error, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Path};
+
+use crate::case::RenameRule;
+
+const RENAME_ALL: &str = "rename_all";
+const RENAME_ALL_USAGE: &str = "#[desenv(rename_all = \"rule\")]";
+
+const TAG: &str = "tag";
+const TAG_USAGE: &str = "#[desenv(tag = \"VAR\")]";
+
+const PREFIX: &str = "prefix";
+const PREFIX_USAGE: &str = "#[desenv(prefix = \"value\")]";
+
+const FILE: &str = "file";
+const FILE_USAGE: &str = "#[desenv(file)]";
+
+pub struct Enum {
+    pub rename_all: Option<RenameRule>,
+    pub tag: Option<String>,
+    pub prefix: Option<String>,
+    pub file: bool,
+}
+
+impl Enum {
+    pub fn from_attrs(attrs: &[Attribute], enum_span: Span) -> Result<Self, Error> {
+        let mut this: Self = Self { rename_all: None, tag: None, prefix: None, file: false };
+
+        if attrs.len() > 1 {
+            return Err(Error::new(
+                attrs.first().unwrap().span(),
+                "cannot have more than one `desenv` attribute per enum",
+            ));
+        }
+
+        let attr: &Attribute = match attrs.first() {
+            None => return Ok(this),
+            Some(attr) => attr,
+        };
+
+        let modifiers = match attr.parse_meta()? {
+            Meta::List(meta_list) => meta_list.nested.into_iter().filter_map(|meta| match meta {
+                NestedMeta::Meta(meta) => Some(meta),
+                NestedMeta::Lit(_) => None,
+            }),
+
+            meta => {
+                return Err(Error::new(
+                    meta.span(),
+                    "`desenv` enum attribute must be used as list of modifiers",
+                ))
+            }
+        };
+
+        for meta in modifiers {
+            let span: Span = meta.span();
+            match meta {
+                Meta::Path(path) => this = parse_path(this, &path, span)?,
+                Meta::NameValue(name_value) => this = parse_name_value(this, name_value, span)?,
+                Meta::List(meta_list) => this = parse_list(this, meta_list, span)?,
+            }
+        }
+
+        this.validate(enum_span)
+    }
+
+    fn validate(self, span: Span) -> Result<Self, Error> {
+        match self {
+            Self { tag: Some(tag), .. } if tag.is_empty() => Err(Error::new(span, "`tag` modifiers must not be empty")),
+            Self { prefix: Some(prefix), .. } if prefix.is_empty() => {
+                Err(Error::new(span, "`prefix` modifiers must not be empty"))
+            }
+            _ => Ok(self),
+        }
+    }
+
+    pub fn get_prefix(&self) -> String {
+        self.prefix.clone().unwrap_or_default()
+    }
+}
+
+fn parse_path(mut this: Enum, path: &Path, span: Span) -> Result<Enum, Error> {
+    let ty: &str = "path";
+
+    match path {
+        _ if path.is_ident(FILE) => this.file = true,
+        _ if path.is_ident(RENAME_ALL) => return Err(Error::new(span, usage_error(ty, RENAME_ALL, RENAME_ALL_USAGE))),
+        _ if path.is_ident(TAG) => return Err(Error::new(span, usage_error(ty, TAG, TAG_USAGE))),
+        _ if path.is_ident(PREFIX) => return Err(Error::new(span, usage_error(ty, PREFIX, PREFIX_USAGE))),
+        _ => return Err(unknown_modifier(path.get_ident(), span)),
+    }
+
+    Ok(this)
+}
+
+fn parse_name_value(mut this: Enum, name_value: MetaNameValue, span: Span) -> Result<Enum, Error> {
+    match name_value {
+        MetaNameValue { path, lit, .. } if path.is_ident(RENAME_ALL) => this.rename_all = Some(rename_rule(&lit)?),
+        MetaNameValue { path, lit: Lit::Str(lit), .. } if path.is_ident(TAG) => this.tag = Some(lit.value()),
+        MetaNameValue { path, lit: Lit::Str(lit), .. } if path.is_ident(PREFIX) => this.prefix = Some(lit.value()),
+        MetaNameValue { path, .. } if path.is_ident(TAG) => {
+            return Err(Error::new(span, lit_error("string", TAG, TAG_USAGE)))
+        }
+        MetaNameValue { path, .. } if path.is_ident(PREFIX) => {
+            return Err(Error::new(span, lit_error("string", PREFIX, PREFIX_USAGE)))
+        }
+        MetaNameValue { path, .. } if path.is_ident(FILE) => {
+            return Err(Error::new(span, usage_error("named value", FILE, FILE_USAGE)))
+        }
+        MetaNameValue { path, .. } => return Err(unknown_modifier(path.get_ident(), span)),
+    }
+
+    Ok(this)
+}
+
+fn parse_list(mut _this: Enum, meta_list: MetaList, span: Span) -> Result<Enum, Error> {
+    let ty: &str = "list";
+
+    match meta_list {
+        MetaList { path, .. } if path.is_ident(RENAME_ALL) => {
+            Err(Error::new(span, usage_error(ty, RENAME_ALL, RENAME_ALL_USAGE)))
+        }
+        MetaList { path, .. } if path.is_ident(TAG) => Err(Error::new(span, usage_error(ty, TAG, TAG_USAGE))),
+        MetaList { path, .. } if path.is_ident(PREFIX) => Err(Error::new(span, usage_error(ty, PREFIX, PREFIX_USAGE))),
+        MetaList { path, .. } if path.is_ident(FILE) => Err(Error::new(span, usage_error(ty, FILE, FILE_USAGE))),
+        MetaList { path, .. } => Err(unknown_modifier(path.get_ident(), span)),
+    }
+}
+
+pub fn rename_rule(lit: &Lit) -> Result<RenameRule, Error> {
+    match lit {
+        Lit::Str(str) => RenameRule::from_name(&str.value()).ok_or_else(|| {
+            Error::new_spanned(
+                lit,
+                format!("unknown `{}` rule. Expected one of {}", RENAME_ALL, RenameRule::USAGE),
+            )
+        }),
+        _ => Err(Error::new_spanned(
+            lit,
+            format!("`{RENAME_ALL}` modifier must contain a string literal"),
+        )),
+    }
+}
+
+fn usage_error(ty: &str, modifier: &str, usage: &str) -> String {
+    format!("`{modifier}` modifier cannot be used as {ty}. Usage: `{usage}`")
+}
+
+fn lit_error(ty: &str, modifier: &str, usage: &str) -> String {
+    format!("`{modifier}` modifier must contain a {ty} literal. Usage: `{usage}`")
+}
+
+fn unknown_modifier(ident: Option<&Ident>, span: Span) -> Error {
+    ident.map_or_else(
+        || Error::new(span, "unknown enum attribute modifier".to_string()),
+        |name| Error::new(span, format!("unknown enum attribute modifier `{name}`")),
+    )
+}
@@ -2,7 +2,11 @@ use proc_macro2::{Ident, Span};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Comma;
-use syn::{Attribute, Error, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Path};
+use syn::{Attribute, Error, Expr, ExprLit, Lit, LitStr, Meta, MetaList, MetaNameValue, NestedMeta, Path};
+
+use super::validate::{parse_rules, Rule};
+use crate::case::RenameRule;
+use crate::ty::Type;
 
 const RENAME: &str = "rename";
 const RENAME_USAGE: &str = "#[desenv(rename = \"value\")]";
@@ -11,22 +15,113 @@ const SEPARATOR: &str = "separator";
 const SEPARATOR_USAGE: &str = "#[desenv(separator = ',')]";
 
 const NESTED: &str = "nested";
-const NESTED_USAGE: &str = "#[desenv(nested)]";
+const NESTED_USAGE: &str = "#[desenv(nested)] or #[desenv(nested(prefix = \"value\"))]";
+
+const FILE: &str = "file";
+const FILE_USAGE: &str = "#[desenv(file)]";
+
+const MAP: &str = "map";
+const MAP_USAGE: &str =
+    "#[desenv(map(key_case = \"lowercase\"))] or #[desenv(map(inline, separator = ';', key_separator = '='))]";
+
+const PARSE_WITH: &str = "parse_with";
+const PARSE_WITH_USAGE: &str = "#[desenv(parse_with = \"path::to::function\")]";
+
+const STRICT_BOOL: &str = "strict_bool";
+const STRICT_BOOL_USAGE: &str = "#[desenv(strict_bool)]";
+
+const SECRET: &str = "secret";
+const SECRET_USAGE: &str = "#[desenv(secret)]";
+
+const VALIDATE: &str = "validate";
+const VALIDATE_USAGE: &str = "#[desenv(validate(non_empty))]";
+
+const VALIDATE_WITH: &str = "validate_with";
+const VALIDATE_WITH_USAGE: &str = "#[desenv(validate_with = \"path::to::function\")]";
+
+const REQUIRES: &str = "requires";
+const REQUIRES_USAGE: &str = "#[desenv(requires = \"field\")]";
+
+const CONFLICTS_WITH: &str = "conflicts_with";
+const CONFLICTS_WITH_USAGE: &str = "#[desenv(conflicts_with = \"field\")]";
+
+const REQUIRED_IF: &str = "required_if";
+const REQUIRED_IF_USAGE: &str = "#[desenv(required_if(field = \"field\", eq = \"value\"))]";
+
+const ALIAS: &str = "alias";
+const ALIAS_USAGE: &str = "#[desenv(alias = \"OLD_NAME\")]";
+
+const DEPRECATED_ALIAS: &str = "deprecated_alias";
+const DEPRECATED_ALIAS_USAGE: &str = "#[desenv(deprecated_alias = \"OLD_NAME\")]";
+
+const DEFAULT_FN: &str = "default_fn";
+const DEFAULT_FN_USAGE: &str = "#[desenv(default_fn = \"path::to::function\")]";
+
+const DEFAULT_EXPR: &str = "default_expr";
+const DEFAULT_EXPR_USAGE: &str =
+    "#[desenv(default_expr = 8080)] or #[desenv(default_expr = \"Duration::from_secs(30)\")]";
+
+const KIND: &str = "kind";
+const KIND_USAGE: &str = "#[desenv(kind = \"vec\")]";
 
 const DEFAULT: &str = "default";
 const DEFAULT_USAGE: &str =
-    "#[desenv(default)], #[desenv(default = \"value\")], #[desenv(default(value = \"value\"))] or #[desenv(default(env = \"ENV\"))]";
+    "#[desenv(default)], #[desenv(default = \"value\")], #[desenv(default(value = \"value\"))] or #[desenv(default(env = \"ENV\", value = \"value\"))]";
 
+#[derive(Clone)]
 pub struct Field {
     pub rename: Option<String>,
     pub default: Option<Default>,
     pub separator: Option<char>,
-    pub nested: bool,
+    pub nested: Option<Nested>,
+    pub file: bool,
+    pub map: Option<Map>,
+    pub kind: Option<Type>,
+    pub parse_with: Option<Path>,
+    pub strict_bool: bool,
+    pub secret: bool,
+    pub validate: Vec<Rule>,
+    pub validate_with: Option<Path>,
+    pub requires: Vec<String>,
+    pub conflicts_with: Vec<String>,
+    pub required_if: Vec<(String, String)>,
+    pub aliases: Vec<Alias>,
+}
+
+/// The `nested` modifier, optionally setting the prefix of the nested struct variables.
+#[derive(Clone)]
+pub struct Nested {
+    pub prefix: Option<String>,
+}
+
+/// Another name the variable of a field is looked up with, if the primary one is missing. Loading a field
+/// from a deprecated alias raises a warning.
+#[derive(Clone)]
+pub struct Alias {
+    pub name: String,
+    pub deprecated: bool,
 }
 
 impl Field {
     pub fn from_attrs(attrs: &[Attribute], field_span: Span) -> Result<Self, Error> {
-        let mut this: Self = Self { rename: None, default: None, separator: None, nested: false };
+        let mut this: Self = Self {
+            rename: None,
+            default: None,
+            separator: None,
+            nested: None,
+            file: false,
+            map: None,
+            kind: None,
+            parse_with: None,
+            strict_bool: false,
+            secret: false,
+            validate: vec![],
+            validate_with: None,
+            requires: vec![],
+            conflicts_with: vec![],
+            required_if: vec![],
+            aliases: vec![],
+        };
 
         if attrs.len() > 1 {
             return Err(Error::new(
@@ -66,42 +161,135 @@ impl Field {
         this.validate(field_span)
     }
 
+    /// Returns the prefix set with the `nested(prefix = "...")` modifier.
+    pub fn nested_prefix(&self) -> Option<&str> {
+        self.nested.as_ref().and_then(|nested| nested.prefix.as_deref())
+    }
+
     fn validate(self, span: Span) -> Result<Self, Error> {
         match self {
-            Self { nested: true, rename, separator, .. } if rename.is_some() || separator.is_some() => Err(Error::new(
+            Self { nested: Some(_), rename, separator, .. } if rename.is_some() || separator.is_some() => {
+                Err(Error::new(
+                    span,
+                    "cannot set `rename` or `separator` modifiers on a field marked as `nested`",
+                ))
+            }
+            Self { nested: Some(_), file: true, .. } => Err(Error::new(
+                span,
+                "cannot set `file` modifier on a field marked as `nested`",
+            )),
+            Self { nested: Some(_), map: Some(_), .. } => Err(Error::new(
+                span,
+                "cannot set `map` modifier on a field marked as `nested`",
+            )),
+            Self { nested: Some(_), parse_with: Some(_), .. } => Err(Error::new(
+                span,
+                "cannot set `parse_with` modifier on a field marked as `nested`",
+            )),
+            Self { nested: Some(_), ref validate, ref validate_with, .. }
+                if !validate.is_empty() || validate_with.is_some() =>
+            {
+                Err(Error::new(
+                    span,
+                    "cannot set `validate` or `validate_with` modifiers on a field marked as `nested`",
+                ))
+            }
+            Self { nested: Some(_), ref aliases, .. } if !aliases.is_empty() => Err(Error::new(
                 span,
-                "cannot set `rename` or `separator` modifiers on a field marked as `nested`",
+                "cannot set `alias` or `deprecated_alias` modifiers on a field marked as `nested`",
             )),
-            Self { nested: true, default: Some(Default::Env(_)), .. } => Err(Error::new(
+            Self { nested: Some(_), default: Some(Default::Env { .. }), .. } => Err(Error::new(
                 span,
                 "cannot set `default` with env modifier on a field marked as `nested`",
             )),
-            Self { nested: true, default: Some(Default::Value(_)), .. } => Err(Error::new(
+            Self { nested: Some(_), default: Some(Default::Value(_)), .. } => Err(Error::new(
                 span,
                 "cannot set `default` with value modifier on a field marked as `nested`",
             )),
+            Self { nested: Some(_), default: Some(Default::Fn(_) | Default::Expr(_)), .. } => Err(Error::new(
+                span,
+                "cannot set `default_fn` or `default_expr` modifiers on a field marked as `nested`",
+            )),
+            Self { nested: Some(Nested { prefix: Some(prefix) }), .. } if prefix.is_empty() => {
+                Err(Error::new(span, "`nested.prefix` modifiers must not be empty"))
+            }
             Self { rename: Some(rename), .. } if rename.is_empty() => {
                 Err(Error::new(span, "`rename` modifiers must not be empty"))
             }
+            Self { ref aliases, .. } if aliases.iter().any(|alias| alias.name.is_empty()) => Err(Error::new(
+                span,
+                "`alias` and `deprecated_alias` modifiers must not be empty",
+            )),
             _ => Ok(self),
         }
     }
 }
 
+/// How a map field is loaded. By default all the variables starting with the field variable name (followed
+/// by `_`) are collected; `inline` maps are parsed from a single `key=value,key=value` variable instead.
+#[derive(Clone, Copy, Default)]
+pub struct Map {
+    pub inline: bool,
+    pub separator: Option<char>,
+    pub key_separator: Option<char>,
+    pub key_case: Option<RenameRule>,
+}
+
+/// The value of a field whose variable is missing. `Value` and `Env` are parsed like the variable, while `Fn`
+/// (the path of a function returning the field type) and `Expr` are typed values checked at compile time: for
+/// `Option<T>` fields they are `Option<T>` too. `Env` holds the fallback variables, looked up in order, and the
+/// value used if none of them is set.
+#[derive(Clone)]
 pub enum Default {
     Std,
     Value(String),
-    Env(String),
+    Env { vars: Vec<String>, value: Option<String> },
+    Fn(Path),
+    Expr(Expr),
+}
+
+impl Default {
+    const fn is_typed(&self) -> bool {
+        matches!(self, Self::Fn(_) | Self::Expr(_))
+    }
 }
 
 fn parse_path(mut this: Field, path: &Path, span: Span) -> Result<Field, Error> {
     let ty: &str = "path";
 
     match path {
-        _ if path.is_ident(DEFAULT) => this.default = Some(Default::Std),
-        _ if path.is_ident(NESTED) => this.nested = true,
+        _ if path.is_ident(DEFAULT) => set_default(&mut this, Default::Std, span)?,
+        _ if path.is_ident(NESTED) => this.nested = Some(Nested { prefix: None }),
+        _ if path.is_ident(FILE) => this.file = true,
+        _ if path.is_ident(STRICT_BOOL) => this.strict_bool = true,
+        _ if path.is_ident(SECRET) => this.secret = true,
+        _ if path.is_ident(DEFAULT_FN) => return Err(Error::new(span, usage_error(ty, DEFAULT_FN, DEFAULT_FN_USAGE))),
+        _ if path.is_ident(DEFAULT_EXPR) => {
+            return Err(Error::new(span, usage_error(ty, DEFAULT_EXPR, DEFAULT_EXPR_USAGE)))
+        }
+        _ if path.is_ident(ALIAS) => return Err(Error::new(span, usage_error(ty, ALIAS, ALIAS_USAGE))),
+        _ if path.is_ident(DEPRECATED_ALIAS) => {
+            return Err(Error::new(
+                span,
+                usage_error(ty, DEPRECATED_ALIAS, DEPRECATED_ALIAS_USAGE),
+            ))
+        }
+        _ if path.is_ident(REQUIRES) => return Err(Error::new(span, usage_error(ty, REQUIRES, REQUIRES_USAGE))),
+        _ if path.is_ident(CONFLICTS_WITH) => {
+            return Err(Error::new(span, usage_error(ty, CONFLICTS_WITH, CONFLICTS_WITH_USAGE)))
+        }
+        _ if path.is_ident(REQUIRED_IF) => {
+            return Err(Error::new(span, usage_error(ty, REQUIRED_IF, REQUIRED_IF_USAGE)))
+        }
+        _ if path.is_ident(VALIDATE) => return Err(Error::new(span, usage_error(ty, VALIDATE, VALIDATE_USAGE))),
+        _ if path.is_ident(VALIDATE_WITH) => {
+            return Err(Error::new(span, usage_error(ty, VALIDATE_WITH, VALIDATE_WITH_USAGE)))
+        }
         _ if path.is_ident(RENAME) => return Err(Error::new(span, usage_error(ty, RENAME, RENAME_USAGE))),
         _ if path.is_ident(SEPARATOR) => return Err(Error::new(span, usage_error(ty, SEPARATOR, SEPARATOR_USAGE))),
+        _ if path.is_ident(MAP) => return Err(Error::new(span, usage_error(ty, MAP, MAP_USAGE))),
+        _ if path.is_ident(KIND) => return Err(Error::new(span, usage_error(ty, KIND, KIND_USAGE))),
+        _ if path.is_ident(PARSE_WITH) => return Err(Error::new(span, usage_error(ty, PARSE_WITH, PARSE_WITH_USAGE))),
         _ => return Err(unknown_modifier(path.get_ident(), span)),
     }
 
@@ -120,7 +308,17 @@ fn parse_name_value(mut this: Field, name_value: MetaNameValue, span: Span) -> R
             this.separator = Some(lit.value());
         }
         MetaNameValue { path, lit: Lit::Str(lit), .. } if path.is_ident(DEFAULT) => {
-            this.default = Some(Default::Value(lit.value()));
+            set_default(&mut this, Default::Value(lit.value()), span)?;
+        }
+        MetaNameValue { path, lit: Lit::Str(lit), .. } if path.is_ident(DEFAULT_FN) => {
+            let function: Path = parse_lit_for_function(&lit, DEFAULT_FN, DEFAULT_FN_USAGE)?;
+            set_default(&mut this, Default::Fn(function), span)?;
+        }
+        MetaNameValue { path, .. } if path.is_ident(DEFAULT_FN) => {
+            return Err(Error::new(span, lit_error("string", DEFAULT_FN, DEFAULT_FN_USAGE)))
+        }
+        MetaNameValue { path, lit, .. } if path.is_ident(DEFAULT_EXPR) => {
+            set_default(&mut this, Default::Expr(parse_lit_for_default_expr(lit)?), span)?;
         }
         MetaNameValue { path, .. } if path.is_ident(DEFAULT) => {
             return Err(Error::new(span, lit_error("string", DEFAULT, DEFAULT_USAGE)))
@@ -131,6 +329,71 @@ fn parse_name_value(mut this: Field, name_value: MetaNameValue, span: Span) -> R
         MetaNameValue { path, .. } if path.is_ident(NESTED) => {
             return Err(Error::new(span, usage_error(ty, NESTED, NESTED_USAGE)))
         }
+        MetaNameValue { path, .. } if path.is_ident(FILE) => {
+            return Err(Error::new(span, usage_error(ty, FILE, FILE_USAGE)))
+        }
+        MetaNameValue { path, lit: Lit::Str(lit), .. } if path.is_ident(KIND) => {
+            this.kind = Some(parse_lit_for_kind(&lit)?);
+        }
+        MetaNameValue { path, lit: Lit::Str(lit), .. } if path.is_ident(PARSE_WITH) => {
+            this.parse_with = Some(parse_lit_for_function(&lit, PARSE_WITH, PARSE_WITH_USAGE)?);
+        }
+        MetaNameValue { path, .. } if path.is_ident(PARSE_WITH) => {
+            return Err(Error::new(span, lit_error("string", PARSE_WITH, PARSE_WITH_USAGE)))
+        }
+        MetaNameValue { path, .. } if path.is_ident(KIND) => {
+            return Err(Error::new(span, lit_error("string", KIND, KIND_USAGE)))
+        }
+        MetaNameValue { path, .. } if path.is_ident(STRICT_BOOL) => {
+            return Err(Error::new(span, usage_error(ty, STRICT_BOOL, STRICT_BOOL_USAGE)))
+        }
+        MetaNameValue { path, lit: Lit::Str(lit), .. } if path.is_ident(ALIAS) || path.is_ident(DEPRECATED_ALIAS) => {
+            let deprecated: bool = path.is_ident(DEPRECATED_ALIAS);
+            this.aliases.push(Alias { name: lit.value(), deprecated });
+        }
+        MetaNameValue { path, .. } if path.is_ident(ALIAS) => {
+            return Err(Error::new(span, lit_error("string", ALIAS, ALIAS_USAGE)))
+        }
+        MetaNameValue { path, .. } if path.is_ident(DEPRECATED_ALIAS) => {
+            return Err(Error::new(
+                span,
+                lit_error("string", DEPRECATED_ALIAS, DEPRECATED_ALIAS_USAGE),
+            ))
+        }
+        MetaNameValue { path, lit: Lit::Str(lit), .. } if path.is_ident(REQUIRES) => this.requires.push(lit.value()),
+        MetaNameValue { path, .. } if path.is_ident(REQUIRES) => {
+            return Err(Error::new(span, lit_error("string", REQUIRES, REQUIRES_USAGE)))
+        }
+        MetaNameValue { path, lit: Lit::Str(lit), .. } if path.is_ident(CONFLICTS_WITH) => {
+            this.conflicts_with.push(lit.value());
+        }
+        MetaNameValue { path, .. } if path.is_ident(CONFLICTS_WITH) => {
+            return Err(Error::new(
+                span,
+                lit_error("string", CONFLICTS_WITH, CONFLICTS_WITH_USAGE),
+            ))
+        }
+        MetaNameValue { path, .. } if path.is_ident(REQUIRED_IF) => {
+            return Err(Error::new(span, usage_error(ty, REQUIRED_IF, REQUIRED_IF_USAGE)))
+        }
+        MetaNameValue { path, lit: Lit::Str(lit), .. } if path.is_ident(VALIDATE_WITH) => {
+            this.validate_with = Some(parse_lit_for_function(&lit, VALIDATE_WITH, VALIDATE_WITH_USAGE)?);
+        }
+        MetaNameValue { path, .. } if path.is_ident(VALIDATE_WITH) => {
+            return Err(Error::new(
+                span,
+                lit_error("string", VALIDATE_WITH, VALIDATE_WITH_USAGE),
+            ))
+        }
+        MetaNameValue { path, .. } if path.is_ident(VALIDATE) => {
+            return Err(Error::new(span, usage_error(ty, VALIDATE, VALIDATE_USAGE)))
+        }
+        MetaNameValue { path, .. } if path.is_ident(SECRET) => {
+            return Err(Error::new(span, usage_error(ty, SECRET, SECRET_USAGE)))
+        }
+        MetaNameValue { path, .. } if path.is_ident(MAP) => {
+            return Err(Error::new(span, usage_error(ty, MAP, MAP_USAGE)))
+        }
         MetaNameValue { path, .. } => return Err(unknown_modifier(path.get_ident(), span)),
     }
 
@@ -142,7 +405,13 @@ fn parse_list(mut this: Field, meta_list: MetaList, span: Span) -> Result<Field,
 
     match meta_list {
         MetaList { path, nested, .. } if path.is_ident(DEFAULT) => {
-            this.default = Some(parse_nested_meta_for_default(&path, &nested)?);
+            set_default(&mut this, parse_nested_meta_for_default(&path, &nested)?, span)?;
+        }
+        MetaList { path, .. } if path.is_ident(DEFAULT_FN) => {
+            return Err(Error::new(span, usage_error(ty, DEFAULT_FN, DEFAULT_FN_USAGE)))
+        }
+        MetaList { path, .. } if path.is_ident(DEFAULT_EXPR) => {
+            return Err(Error::new(span, usage_error(ty, DEFAULT_EXPR, DEFAULT_EXPR_USAGE)))
         }
         MetaList { path, .. } if path.is_ident(RENAME) => {
             return Err(Error::new(span, usage_error(ty, RENAME, RENAME_USAGE)))
@@ -150,8 +419,51 @@ fn parse_list(mut this: Field, meta_list: MetaList, span: Span) -> Result<Field,
         MetaList { path, .. } if path.is_ident(SEPARATOR) => {
             return Err(Error::new(span, usage_error(ty, SEPARATOR, SEPARATOR_USAGE)))
         }
-        MetaList { path, .. } if path.is_ident(NESTED) => {
-            return Err(Error::new(span, usage_error(ty, NESTED, NESTED_USAGE)))
+        MetaList { path, nested, .. } if path.is_ident(NESTED) => {
+            this.nested = Some(Nested { prefix: Some(parse_nested_meta_for_nested(&path, &nested)?) });
+        }
+        MetaList { path, .. } if path.is_ident(FILE) => {
+            return Err(Error::new(span, usage_error(ty, FILE, FILE_USAGE)))
+        }
+        MetaList { path, nested, .. } if path.is_ident(MAP) => {
+            this.map = Some(parse_nested_meta_for_map(&path, &nested)?);
+        }
+        MetaList { path, .. } if path.is_ident(PARSE_WITH) => {
+            return Err(Error::new(span, usage_error(ty, PARSE_WITH, PARSE_WITH_USAGE)))
+        }
+        MetaList { path, .. } if path.is_ident(STRICT_BOOL) => {
+            return Err(Error::new(span, usage_error(ty, STRICT_BOOL, STRICT_BOOL_USAGE)))
+        }
+        MetaList { path, nested, .. } if path.is_ident(REQUIRED_IF) => {
+            this.required_if
+                .push(parse_nested_meta_for_required_if(&path, &nested)?);
+        }
+        MetaList { path, .. } if path.is_ident(ALIAS) => {
+            return Err(Error::new(span, usage_error(ty, ALIAS, ALIAS_USAGE)))
+        }
+        MetaList { path, .. } if path.is_ident(DEPRECATED_ALIAS) => {
+            return Err(Error::new(
+                span,
+                usage_error(ty, DEPRECATED_ALIAS, DEPRECATED_ALIAS_USAGE),
+            ))
+        }
+        MetaList { path, .. } if path.is_ident(REQUIRES) => {
+            return Err(Error::new(span, usage_error(ty, REQUIRES, REQUIRES_USAGE)))
+        }
+        MetaList { path, .. } if path.is_ident(CONFLICTS_WITH) => {
+            return Err(Error::new(span, usage_error(ty, CONFLICTS_WITH, CONFLICTS_WITH_USAGE)))
+        }
+        MetaList { path, nested, .. } if path.is_ident(VALIDATE) => {
+            this.validate.extend(parse_rules(&path, &nested)?);
+        }
+        MetaList { path, .. } if path.is_ident(VALIDATE_WITH) => {
+            return Err(Error::new(span, usage_error(ty, VALIDATE_WITH, VALIDATE_WITH_USAGE)))
+        }
+        MetaList { path, .. } if path.is_ident(SECRET) => {
+            return Err(Error::new(span, usage_error(ty, SECRET, SECRET_USAGE)))
+        }
+        MetaList { path, .. } if path.is_ident(KIND) => {
+            return Err(Error::new(span, usage_error(ty, KIND, KIND_USAGE)))
         }
         MetaList { path, .. } => return Err(unknown_modifier(path.get_ident(), span)),
     }
@@ -159,47 +471,227 @@ fn parse_list(mut this: Field, meta_list: MetaList, span: Span) -> Result<Field,
     Ok(this)
 }
 
+// Set the default of the field. The typed defaults (`default_fn` and `default_expr`) cannot be combined with other
+// defaults, since it would not be clear which one is used.
+fn set_default(this: &mut Field, default: Default, span: Span) -> Result<(), Error> {
+    match &this.default {
+        Some(current) if current.is_typed() || default.is_typed() => Err(Error::new(
+            span,
+            "only one of `default`, `default_fn` and `default_expr` modifiers can be set",
+        )),
+        _ => {
+            this.default = Some(default);
+            Ok(())
+        }
+    }
+}
+
+// Parse the `default(env = "A", env = "B", value = "value")` entries: the variables are looked up in order and
+// the value, which must be the last entry, is used if none of them is set.
 fn parse_nested_meta_for_default(path: &Path, nested: &Punctuated<NestedMeta, Comma>) -> Result<Default, Error> {
+    let mut vars: Vec<String> = vec![];
+    let mut value: Option<String> = None;
+
+    if nested.is_empty() {
+        let message = "`default` modifier must contain at least one entry".to_string();
+        return Err(Error::new(path.span(), message));
+    }
+
+    for entry in nested {
+        if value.is_some() {
+            let message = "`default.value` modifier must be the last entry of `default`".to_string();
+            return Err(Error::new(entry.span(), message));
+        }
+
+        match entry {
+            NestedMeta::Meta(meta) => match meta {
+                Meta::NameValue(MetaNameValue { path: name, lit, .. }) if name.is_ident("env") => match lit {
+                    Lit::Str(str) => vars.push(str.value()),
+                    _ => {
+                        return Err(Error::new_spanned(
+                            lit,
+                            "`default.env` modifier must contain a string literal",
+                        ))
+                    }
+                },
+                Meta::NameValue(MetaNameValue { path: name, lit, .. }) if name.is_ident("value") => match lit {
+                    Lit::Str(str) => value = Some(str.value()),
+                    _ => {
+                        return Err(Error::new_spanned(
+                            lit,
+                            "`default.value` modifier must contain a string literal",
+                        ))
+                    }
+                },
+                Meta::NameValue(MetaNameValue { path: name, .. }) => {
+                    return Err(unknown_modifier(name.get_ident(), name.span()))
+                }
+                Meta::Path(_) => {
+                    let message = "`default` modifier cannot contain a path modifier".to_string();
+                    return Err(Error::new(path.span(), message));
+                }
+                Meta::List(_) => {
+                    let message = "`default` modifier cannot contain a list modifier".to_string();
+                    return Err(Error::new(path.span(), message));
+                }
+            },
+            NestedMeta::Lit(_) => {
+                let message = "`default` modifier must contain only non literal entries".to_string();
+                return Err(Error::new(path.span(), message));
+            }
+        }
+    }
+
+    match value {
+        Some(value) if vars.is_empty() => Ok(Default::Value(value)),
+        value => Ok(Default::Env { vars, value }),
+    }
+}
+
+fn parse_nested_meta_for_nested(path: &Path, nested: &Punctuated<NestedMeta, Comma>) -> Result<String, Error> {
     if nested.len() > 1 {
-        let message: String = "`default` modifier cannot contain more than one modifier".to_string();
+        let message: String = "`nested` modifier cannot contain more than one modifier".to_string();
         return Err(Error::new(path.span(), message));
     }
 
     match nested.first() {
-        Some(NestedMeta::Meta(meta)) => match meta {
-            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("env") => match lit {
-                Lit::Str(str) => Ok(Default::Env(str.value())),
+        Some(NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. }))) if path.is_ident("prefix") => {
+            match lit {
+                Lit::Str(str) => Ok(str.value()),
                 _ => Err(Error::new_spanned(
                     lit,
-                    "`default.env` modifier must contain a string literal",
+                    "`nested.prefix` modifier must contain a string literal",
                 )),
-            },
-            Meta::NameValue(MetaNameValue { path, lit, .. }) if path.is_ident("value") => match lit {
-                Lit::Str(str) => Ok(Default::Value(str.value())),
-                _ => Err(Error::new_spanned(
+            }
+        }
+        Some(NestedMeta::Meta(meta)) => Err(Error::new(
+            meta.span(),
+            format!("`{NESTED}` modifier only accepts `prefix`. Usage: `{NESTED_USAGE}`"),
+        )),
+        Some(NestedMeta::Lit(_)) | None => Err(Error::new(path.span(), usage_error("list", NESTED, NESTED_USAGE))),
+    }
+}
+
+fn parse_nested_meta_for_map(path: &Path, nested: &Punctuated<NestedMeta, Comma>) -> Result<Map, Error> {
+    let mut map: Map = Map::default();
+
+    if nested.is_empty() {
+        return Err(Error::new(path.span(), usage_error("list", MAP, MAP_USAGE)));
+    }
+
+    for meta in nested {
+        match meta {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("inline") => map.inline = true,
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) if path.is_ident("separator") => {
+                map.inline = true;
+                map.separator = Some(char_lit(lit, "`map.separator` modifier must contain a char literal")?);
+            }
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) if path.is_ident("key_separator") => {
+                map.inline = true;
+                map.key_separator = Some(char_lit(
                     lit,
-                    "`default.value` modifier must contain a string literal",
-                )),
-            },
-            Meta::NameValue(MetaNameValue { path, .. }) => Err(unknown_modifier(path.get_ident(), path.span())),
-            Meta::Path(_) => {
-                let message = "`default` modifier cannot contain a path modifier".to_string();
-                Err(Error::new(path.span(), message))
+                    "`map.key_separator` modifier must contain a char literal",
+                )?);
             }
-            Meta::List(_) => {
-                let message = "`default` modifier cannot contain a list modifier".to_string();
-                Err(Error::new(path.span(), message))
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) if path.is_ident("key_case") => {
+                map.key_case = Some(key_case(lit)?);
             }
-        },
-        Some(NestedMeta::Lit(_)) => {
-            let message = "`default` modifier must contain exactly one non literal entry".to_string();
-            Err(Error::new(path.span(), message))
+            NestedMeta::Meta(meta) => return Err(unknown_modifier(meta.path().get_ident(), meta.span())),
+            NestedMeta::Lit(_) => return Err(Error::new(path.span(), usage_error("list", MAP, MAP_USAGE))),
         }
-        None => {
-            let message = "`default` modifier must contain exactly one entry".to_string();
-            Err(Error::new(path.span(), message))
+    }
+
+    Ok(map)
+}
+
+fn parse_nested_meta_for_required_if(
+    path: &Path,
+    nested: &Punctuated<NestedMeta, Comma>,
+) -> Result<(String, String), Error> {
+    let (mut field, mut eq): (Option<String>, Option<String>) = (None, None);
+
+    for meta in nested {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(lit), .. }))
+                if path.is_ident("field") =>
+            {
+                field = Some(lit.value());
+            }
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(lit), .. }))
+                if path.is_ident("eq") =>
+            {
+                eq = Some(lit.value());
+            }
+            _ => {
+                return Err(Error::new(
+                    meta.span(),
+                    usage_error("list", REQUIRED_IF, REQUIRED_IF_USAGE),
+                ))
+            }
         }
     }
+
+    match (field, eq) {
+        (Some(field), Some(eq)) => Ok((field, eq)),
+        _ => Err(Error::new(
+            path.span(),
+            format!("`{REQUIRED_IF}` modifier must contain both `field` and `eq`. Usage: `{REQUIRED_IF_USAGE}`"),
+        )),
+    }
+}
+
+fn parse_lit_for_function(lit: &LitStr, modifier: &str, usage: &str) -> Result<Path, Error> {
+    lit.parse::<Path>().map_err(|_| {
+        let message: String = format!("`{modifier}` modifier must contain a path to a function. Usage: `{usage}`");
+        Error::new_spanned(lit, message)
+    })
+}
+
+// String literals hold the expression, while other literals are the expression.
+fn parse_lit_for_default_expr(lit: Lit) -> Result<Expr, Error> {
+    match lit {
+        Lit::Str(lit) => lit.parse::<Expr>().map_err(|error| {
+            let message: String = format!(
+                "`{DEFAULT_EXPR}` modifier must contain a valid expression ({error}). Usage: `{DEFAULT_EXPR_USAGE}`"
+            );
+            Error::new_spanned(&lit, message)
+        }),
+        lit => Ok(Expr::Lit(ExprLit { attrs: vec![], lit })),
+    }
+}
+
+fn parse_lit_for_kind(lit: &LitStr) -> Result<Type, Error> {
+    Type::from_name(lit.value().as_str())
+        .ok_or_else(|| Error::new_spanned(lit, format!("unknown `{KIND}` value. Expected one of {}", Type::USAGE)))
+}
+
+fn char_lit(lit: &Lit, message: &str) -> Result<char, Error> {
+    match lit {
+        Lit::Char(lit) => Ok(lit.value()),
+        _ => Err(Error::new_spanned(lit, message)),
+    }
+}
+
+// Map keys are cased at runtime, where words can only be split on `_` and `-`: rules joining words without a
+// separator but keeping their boundaries are not available.
+fn key_case(lit: &Lit) -> Result<RenameRule, Error> {
+    match lit {
+        Lit::Str(str) => match RenameRule::from_name(str.value().as_str()) {
+            Some(RenameRule::Pascal | RenameRule::Camel) => Err(Error::new_spanned(
+                lit,
+                "`map.key_case` modifier cannot be \"PascalCase\" or \"camelCase\"",
+            )),
+            Some(rule) => Ok(rule),
+            None => Err(Error::new_spanned(
+                lit,
+                format!("`map.key_case` modifier must be one of {}", RenameRule::USAGE),
+            )),
+        },
+        _ => Err(Error::new_spanned(
+            lit,
+            "`map.key_case` modifier must contain a string literal",
+        )),
+    }
 }
 
 fn usage_error(ty: &str, modifier: &str, usage: &str) -> String {
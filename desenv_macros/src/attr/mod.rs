@@ -1,6 +1,13 @@
 pub use field::Default;
 pub use field::Field;
+pub use field::Map;
+pub use r#enum::Enum;
 pub use r#struct::Struct;
+pub use validate::Rule;
+pub use variant::Variant;
 
+mod r#enum;
 mod field;
 mod r#struct;
+mod validate;
+mod variant;
@@ -2,16 +2,31 @@ use proc_macro2::{Ident, Span};
 use syn::spanned::Spanned;
 use syn::{Attribute, Error, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Path};
 
+use super::r#enum::rename_rule;
+use crate::case::RenameRule;
+
 const PREFIX: &str = "prefix";
 const PREFIX_USAGE: &str = "#[desenv(prefix = \"value\")]";
 
+const FILE: &str = "file";
+const FILE_USAGE: &str = "#[desenv(file)]";
+
+const STRICT_BOOL: &str = "strict_bool";
+const STRICT_BOOL_USAGE: &str = "#[desenv(strict_bool)]";
+
+const RENAME_ALL: &str = "rename_all";
+const RENAME_ALL_USAGE: &str = "#[desenv(rename_all = \"rule\")]";
+
 pub struct Struct {
     pub prefix: Option<String>,
+    pub file: bool,
+    pub rename_all: Option<RenameRule>,
+    pub strict_bool: bool,
 }
 
 impl Struct {
     pub fn from_attrs(attrs: &[Attribute], struct_span: Span) -> Result<Self, Error> {
-        let mut this: Self = Self { prefix: None };
+        let mut this: Self = Self { prefix: None, file: false, rename_all: None, strict_bool: false };
 
         if attrs.len() > 1 {
             return Err(Error::new(
@@ -53,7 +68,7 @@ impl Struct {
 
     fn validate(self, span: Span) -> Result<Self, Error> {
         match self {
-            Self { prefix: Some(prefix) } if prefix.is_empty() => {
+            Self { prefix: Some(prefix), .. } if prefix.is_empty() => {
                 Err(Error::new(span, "`prefix` modifiers must not be empty"))
             }
             _ => Ok(self),
@@ -65,11 +80,18 @@ impl Struct {
     }
 }
 
-fn parse_path(mut _this: Struct, path: &Path, span: Span) -> Result<Struct, Error> {
+fn parse_path(mut this: Struct, path: &Path, span: Span) -> Result<Struct, Error> {
     match path {
-        _ if path.is_ident(PREFIX) => Err(Error::new(span, usage_error("path", PREFIX, PREFIX_USAGE))),
-        _ => Err(unknown_modifier(path.get_ident(), span)),
+        _ if path.is_ident(FILE) => this.file = true,
+        _ if path.is_ident(STRICT_BOOL) => this.strict_bool = true,
+        _ if path.is_ident(PREFIX) => return Err(Error::new(span, usage_error("path", PREFIX, PREFIX_USAGE))),
+        _ if path.is_ident(RENAME_ALL) => {
+            return Err(Error::new(span, usage_error("path", RENAME_ALL, RENAME_ALL_USAGE)))
+        }
+        _ => return Err(unknown_modifier(path.get_ident(), span)),
     }
+
+    Ok(this)
 }
 
 fn parse_name_value(mut this: Struct, name_value: MetaNameValue, span: Span) -> Result<Struct, Error> {
@@ -83,6 +105,16 @@ fn parse_name_value(mut this: Struct, name_value: MetaNameValue, span: Span) ->
                 ))
             }
         },
+        MetaNameValue { path, lit, .. } if path.is_ident(RENAME_ALL) => this.rename_all = Some(rename_rule(&lit)?),
+        MetaNameValue { path, .. } if path.is_ident(FILE) => {
+            return Err(Error::new(span, usage_error("named value", FILE, FILE_USAGE)));
+        }
+        MetaNameValue { path, .. } if path.is_ident(STRICT_BOOL) => {
+            return Err(Error::new(
+                span,
+                usage_error("named value", STRICT_BOOL, STRICT_BOOL_USAGE),
+            ));
+        }
         MetaNameValue { path, .. } => {
             return Err(unknown_modifier(path.get_ident(), span));
         }
@@ -96,6 +128,13 @@ fn parse_list(mut _this: Struct, meta_list: MetaList, span: Span) -> Result<Stru
         MetaList { path, .. } if path.is_ident(PREFIX) => {
             Err(Error::new(span, usage_error("list", PREFIX, PREFIX_USAGE)))
         }
+        MetaList { path, .. } if path.is_ident(RENAME_ALL) => {
+            Err(Error::new(span, usage_error("list", RENAME_ALL, RENAME_ALL_USAGE)))
+        }
+        MetaList { path, .. } if path.is_ident(FILE) => Err(Error::new(span, usage_error("list", FILE, FILE_USAGE))),
+        MetaList { path, .. } if path.is_ident(STRICT_BOOL) => {
+            Err(Error::new(span, usage_error("list", STRICT_BOOL, STRICT_BOOL_USAGE)))
+        }
         MetaList { path, .. } => Err(unknown_modifier(path.get_ident(), span)),
     }
 }
@@ -0,0 +1,143 @@
+use proc_macro2::Ident;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::token::Comma;
+use syn::{Error, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Path};
+
+const VALIDATE_USAGE: &str = "#[desenv(validate(range(min = 1, max = 10), non_empty, len(min = 1, max = 10), \
+                              one_of(\"a\", \"b\"), regex = \"pattern\"))]";
+
+/// A rule of the `validate` field modifier, checked after the value is parsed.
+#[derive(Clone)]
+pub enum Rule {
+    Range { min: Option<Lit>, max: Option<Lit> },
+    Len { min: Option<usize>, max: Option<usize> },
+    NonEmpty,
+    OneOf(Vec<Lit>),
+    Regex(String),
+}
+
+pub fn parse_rules(path: &Path, nested: &Punctuated<NestedMeta, Comma>) -> Result<Vec<Rule>, Error> {
+    if nested.is_empty() {
+        return Err(Error::new(
+            path.span(),
+            format!("`validate` modifier must contain at least one rule. Usage: `{VALIDATE_USAGE}`"),
+        ));
+    }
+
+    nested
+        .iter()
+        .map(|meta| match meta {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("non_empty") => Ok(Rule::NonEmpty),
+            NestedMeta::Meta(Meta::List(MetaList { path, nested, .. })) if path.is_ident("range") => {
+                let (min, max) = parse_bounds(path, nested)?;
+                Ok(Rule::Range { min, max })
+            }
+            NestedMeta::Meta(Meta::List(MetaList { path, nested, .. })) if path.is_ident("len") => {
+                let (min, max) = parse_bounds(path, nested)?;
+                Ok(Rule::Len {
+                    min: min.map(|min| parse_usize(&min)).transpose()?,
+                    max: max.map(|max| parse_usize(&max)).transpose()?,
+                })
+            }
+            NestedMeta::Meta(Meta::List(MetaList { path, nested, .. })) if path.is_ident("one_of") => {
+                let values: Vec<Lit> = nested
+                    .iter()
+                    .map(|value| match value {
+                        NestedMeta::Lit(lit) => Ok(lit.clone()),
+                        NestedMeta::Meta(meta) => {
+                            Err(Error::new(meta.span(), "`validate.one_of` rule must contain literals"))
+                        }
+                    })
+                    .collect::<Result<_, Error>>()?;
+                if values.is_empty() {
+                    return Err(Error::new(
+                        path.span(),
+                        "`validate.one_of` rule must contain at least one value",
+                    ));
+                }
+                Ok(Rule::OneOf(values))
+            }
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) if path.is_ident("regex") => match lit {
+                Lit::Str(str) => {
+                    // The pattern is checked here when the `regex` feature is enabled, instead of failing at runtime.
+                    #[cfg(feature = "regex")]
+                    regex_lite::Regex::new(&str.value()).map_err(|error| {
+                        Error::new_spanned(
+                            str,
+                            format!("`validate.regex` rule contains an invalid pattern: {error}"),
+                        )
+                    })?;
+                    Ok(Rule::Regex(str.value()))
+                }
+                _ => Err(Error::new_spanned(
+                    lit,
+                    "`validate.regex` rule must contain a string literal",
+                )),
+            },
+            NestedMeta::Meta(meta) => Err(unknown_rule(meta.path().get_ident(), meta.span())),
+            NestedMeta::Lit(lit) => Err(Error::new_spanned(
+                lit,
+                format!("`validate` modifier cannot contain literals. Usage: `{VALIDATE_USAGE}`"),
+            )),
+        })
+        .collect()
+}
+
+// Parse the `min` and `max` bounds of the `range` and `len` rules. At least one of them must be set.
+fn parse_bounds(path: &Path, nested: &Punctuated<NestedMeta, Comma>) -> Result<(Option<Lit>, Option<Lit>), Error> {
+    let rule: String = path.get_ident().map(ToString::to_string).unwrap_or_default();
+    let (mut min, mut max): (Option<Lit>, Option<Lit>) = (None, None);
+
+    for meta in nested {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) if path.is_ident("min") => {
+                min = Some(lit.clone());
+            }
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) if path.is_ident("max") => {
+                max = Some(lit.clone());
+            }
+            _ => {
+                return Err(Error::new(
+                    meta.span(),
+                    format!("`validate.{rule}` rule accepts only `min` and `max`"),
+                ))
+            }
+        }
+    }
+
+    if min.is_none() && max.is_none() {
+        return Err(Error::new(
+            path.span(),
+            format!("`validate.{rule}` rule must contain `min`, `max` or both"),
+        ));
+    }
+
+    for lit in min.iter().chain(max.iter()) {
+        if !matches!(lit, Lit::Int(_) | Lit::Float(_)) {
+            return Err(Error::new_spanned(
+                lit,
+                format!("`validate.{rule}` bounds must be number literals"),
+            ));
+        }
+    }
+
+    Ok((min, max))
+}
+
+fn parse_usize(lit: &Lit) -> Result<usize, Error> {
+    match lit {
+        Lit::Int(int) => int.base10_parse(),
+        _ => Err(Error::new_spanned(
+            lit,
+            "`validate.len` bounds must be integer literals",
+        )),
+    }
+}
+
+fn unknown_rule(ident: Option<&Ident>, span: proc_macro2::Span) -> Error {
+    ident.map_or_else(
+        || Error::new(span, "unknown validation rule".to_string()),
+        |name| Error::new(span, format!("unknown validation rule `{name}`")),
+    )
+}
@@ -0,0 +1,133 @@
+use proc_macro2::{Ident, Span};
+use syn::spanned::Spanned;
+use syn::{Attribute, Error, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Path};
+
+const RENAME: &str = "rename";
+const RENAME_USAGE: &str = "#[desenv(rename = \"value\")]";
+
+const ALIAS: &str = "alias";
+const ALIAS_USAGE: &str = "#[desenv(alias = \"value\")]";
+
+const PREFIX: &str = "prefix";
+const PREFIX_USAGE: &str = "#[desenv(prefix = \"value\")]";
+
+pub struct Variant {
+    pub rename: Option<String>,
+    pub aliases: Vec<String>,
+    pub prefix: Option<String>,
+}
+
+impl Variant {
+    pub fn from_attrs(attrs: &[Attribute], variant_span: Span) -> Result<Self, Error> {
+        let mut this: Self = Self { rename: None, aliases: vec![], prefix: None };
+
+        if attrs.len() > 1 {
+            return Err(Error::new(
+                variant_span,
+                "cannot have more than one `desenv` attribute per variant",
+            ));
+        }
+
+        let attr: &Attribute = match attrs.first() {
+            None => return Ok(this),
+            Some(attr) => attr,
+        };
+
+        let modifiers = match attr.parse_meta()? {
+            Meta::List(meta_list) => meta_list.nested.into_iter().filter_map(|meta| match meta {
+                NestedMeta::Meta(meta) => Some(meta),
+                NestedMeta::Lit(_) => None,
+            }),
+
+            meta => {
+                return Err(Error::new(
+                    meta.span(),
+                    "`desenv` variant attribute must be used as list of modifiers",
+                ))
+            }
+        };
+
+        for meta in modifiers {
+            let span: Span = meta.span();
+            match meta {
+                Meta::Path(path) => this = parse_path(this, &path, span)?,
+                Meta::NameValue(name_value) => this = parse_name_value(this, name_value, span)?,
+                Meta::List(meta_list) => this = parse_list(this, meta_list, span)?,
+            }
+        }
+
+        this.validate(variant_span)
+    }
+
+    fn validate(self, span: Span) -> Result<Self, Error> {
+        match self {
+            Self { rename: Some(rename), .. } if rename.is_empty() => {
+                Err(Error::new(span, "`rename` modifiers must not be empty"))
+            }
+            Self { aliases, .. } if aliases.iter().any(String::is_empty) => {
+                Err(Error::new(span, "`alias` modifiers must not be empty"))
+            }
+            Self { prefix: Some(prefix), .. } if prefix.is_empty() => {
+                Err(Error::new(span, "`prefix` modifiers must not be empty"))
+            }
+            _ => Ok(self),
+        }
+    }
+}
+
+fn parse_path(mut _this: Variant, path: &Path, span: Span) -> Result<Variant, Error> {
+    let ty: &str = "path";
+
+    match path {
+        _ if path.is_ident(RENAME) => Err(Error::new(span, usage_error(ty, RENAME, RENAME_USAGE))),
+        _ if path.is_ident(ALIAS) => Err(Error::new(span, usage_error(ty, ALIAS, ALIAS_USAGE))),
+        _ if path.is_ident(PREFIX) => Err(Error::new(span, usage_error(ty, PREFIX, PREFIX_USAGE))),
+        _ => Err(unknown_modifier(path.get_ident(), span)),
+    }
+}
+
+fn parse_name_value(mut this: Variant, name_value: MetaNameValue, span: Span) -> Result<Variant, Error> {
+    match name_value {
+        MetaNameValue { path, lit: Lit::Str(lit), .. } if path.is_ident(RENAME) => this.rename = Some(lit.value()),
+        MetaNameValue { path, .. } if path.is_ident(RENAME) => {
+            return Err(Error::new(span, lit_error("string", RENAME, RENAME_USAGE)))
+        }
+        MetaNameValue { path, lit: Lit::Str(lit), .. } if path.is_ident(ALIAS) => this.aliases.push(lit.value()),
+        MetaNameValue { path, .. } if path.is_ident(ALIAS) => {
+            return Err(Error::new(span, lit_error("string", ALIAS, ALIAS_USAGE)))
+        }
+        MetaNameValue { path, lit: Lit::Str(lit), .. } if path.is_ident(PREFIX) => this.prefix = Some(lit.value()),
+        MetaNameValue { path, .. } if path.is_ident(PREFIX) => {
+            return Err(Error::new(span, lit_error("string", PREFIX, PREFIX_USAGE)))
+        }
+        MetaNameValue { path, .. } => return Err(unknown_modifier(path.get_ident(), span)),
+    }
+
+    Ok(this)
+}
+
+fn parse_list(mut _this: Variant, meta_list: MetaList, span: Span) -> Result<Variant, Error> {
+    let ty: &str = "list";
+
+    match meta_list {
+        MetaList { path, .. } if path.is_ident(RENAME) => Err(Error::new(span, usage_error(ty, RENAME, RENAME_USAGE))),
+        MetaList { path, .. } if path.is_ident(ALIAS) => Err(Error::new(span, usage_error(ty, ALIAS, ALIAS_USAGE))),
+        MetaList { path, .. } if path.is_ident(PREFIX) => Err(Error::new(span, usage_error(ty, PREFIX, PREFIX_USAGE))),
+        MetaList { path, .. } => Err(unknown_modifier(path.get_ident(), span)),
+    }
+}
+
+fn usage_error(ty: &str, modifier: &str, usage: &str) -> String {
+    format!("`{modifier}` modifier cannot be used as {ty}. Usage: `{usage}`")
+}
+
+fn lit_error(ty: &str, modifier: &str, usage: &str) -> String {
+    format!("`{modifier}` modifier must contain a {ty} literal. Usage: `{usage}`")
+}
+
+fn unknown_modifier(ident: Option<&Ident>, span: Span) -> Error {
+    ident.map_or_else(
+        || Error::new(span, "unknown variant attribute modifier".to_string()),
+        |name| Error::new(span, format!("unknown variant attribute modifier `{name}`")),
+    )
+}
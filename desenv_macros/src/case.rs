@@ -0,0 +1,99 @@
+/// The casing rules that can be used in the `rename_all` modifier.
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    pub const USAGE: &'static str = "\"lowercase\", \"UPPERCASE\", \"PascalCase\", \"camelCase\", \"snake_case\", \
+                                     \"SCREAMING_SNAKE_CASE\", \"kebab-case\" or \"SCREAMING-KEBAB-CASE\"";
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "lowercase" => Some(Self::Lower),
+            "UPPERCASE" => Some(Self::Upper),
+            "PascalCase" => Some(Self::Pascal),
+            "camelCase" => Some(Self::Camel),
+            "snake_case" => Some(Self::Snake),
+            "SCREAMING_SNAKE_CASE" => Some(Self::ScreamingSnake),
+            "kebab-case" => Some(Self::Kebab),
+            "SCREAMING-KEBAB-CASE" => Some(Self::ScreamingKebab),
+            _ => None,
+        }
+    }
+
+    /// Apply the rule to the given identifier, either written in `snake_case` (fields) or in `PascalCase`
+    /// (variants).
+    pub fn apply(self, ident: &str) -> String {
+        let words: Vec<String> = words(ident);
+
+        match self {
+            Self::Lower => words.concat().to_lowercase(),
+            Self::Upper => words.concat().to_uppercase(),
+            Self::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+            Self::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(index, word)| {
+                    if index == 0 {
+                        word.to_lowercase()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+            Self::Snake => words.join("_").to_lowercase(),
+            Self::ScreamingSnake => words.join("_").to_uppercase(),
+            Self::Kebab => words.join("-").to_lowercase(),
+            Self::ScreamingKebab => words.join("-").to_uppercase(),
+        }
+    }
+}
+
+// Split an identifier in words on `_`, `-` and on case changes (ex. `HttpServer` and `HTTPServer` are both
+// split in `Http`/`HTTP` and `Server`).
+fn words(ident: &str) -> Vec<String> {
+    let chars: Vec<char> = ident.chars().collect();
+    let mut words: Vec<String> = vec![];
+    let mut word: String = String::new();
+
+    for (index, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+
+        let previous: Option<char> = index.checked_sub(1).map(|index| chars[index]);
+        let next: Option<char> = chars.get(index + 1).copied();
+        let is_boundary: bool = c.is_uppercase()
+            && (previous.map_or(false, |p| p.is_lowercase() || p.is_ascii_digit())
+                || (previous.map_or(false, char::is_uppercase) && next.map_or(false, char::is_lowercase)));
+
+        if is_boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(c);
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect()
+    })
+}
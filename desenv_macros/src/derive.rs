@@ -1,165 +1,885 @@
 use proc_macro2::{Ident, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Comma;
-use syn::Error;
+use syn::{Error, Fields};
 
 use crate::attr;
-use crate::ty::Type;
+use crate::case::RenameRule;
+use crate::ty::{self, Type};
+use crate::value;
 
 const DEFAULT_SEPARATOR: char = ',';
+const DEFAULT_KEY_SEPARATOR: char = '=';
 
 pub fn desenv(
     struct_name: &Ident,
     struct_attr: &attr::Struct,
     fields: &Punctuated<syn::Field, Comma>,
 ) -> Result<TokenStream, Error> {
-    let expanded_fields: Vec<TokenStream> = expand_fields(struct_attr, fields)?;
+    let load_token: TokenStream = load_fields(&quote!(Self), struct_name, struct_attr, fields)?;
+    let check_token: TokenStream = check_fields(struct_name, struct_attr, fields)?;
+    Ok(impl_desenv(struct_name, &load_token, &check_token))
+}
+
+pub fn desenv_enum(
+    enum_name: &Ident,
+    enum_attr: &attr::Enum,
+    variants: &Punctuated<syn::Variant, Comma>,
+) -> Result<TokenStream, Error> {
+    let tag: &String = enum_attr.tag.as_ref().ok_or_else(|| {
+        Error::new(
+            enum_name.span(),
+            "`tag` modifier is required deriving desenv on enums. Usage: `#[desenv(tag = \"VAR\")]`",
+        )
+    })?;
+
+    let mut arms: Vec<TokenStream> = vec![];
+    let mut checks: Vec<TokenStream> = vec![];
+    let mut expected: Vec<String> = vec![];
+
+    for variant in variants {
+        let variant_attr: attr::Variant = attr::Variant::from_attrs(&variant.attrs, variant.span())?;
+        let variant_ident: &Ident = &variant.ident;
+        let values: Vec<String> = value::variant_values(variant_ident, &variant_attr, enum_attr);
+
+        if let Some(value) = values.iter().find(|value| expected.contains(value)) {
+            return Err(Error::new(
+                variant.span(),
+                format!("value `{value}` is accepted by more than one variant"),
+            ));
+        }
+
+        let fields: Punctuated<syn::Field, Comma> = match &variant.fields {
+            Fields::Named(fields) => fields.named.clone(),
+            Fields::Unit => Punctuated::new(),
+            Fields::Unnamed(_) => {
+                return Err(Error::new(
+                    variant.span(),
+                    "desenv could be derived only on enums with named fields or unit variants",
+                ))
+            }
+        };
+
+        // The variant fields are loaded as the fields of a struct with the enum and variant prefixes.
+        let variant_struct_attr: attr::Struct = attr::Struct {
+            prefix: Some(format!(
+                "{}{}",
+                enum_attr.get_prefix(),
+                variant_attr.prefix.unwrap_or_default()
+            )),
+            file: enum_attr.file,
+            rename_all: None,
+            strict_bool: false,
+        };
+        let load_token: TokenStream =
+            load_fields(&quote!(Self::#variant_ident), enum_name, &variant_struct_attr, &fields)?;
+        checks.push(check_fields(enum_name, &variant_struct_attr, &fields)?);
+
+        arms.push(quote!(#(#values)|* => #load_token));
+        expected.extend(values);
+    }
+
+    let current_prefix: TokenStream = current_prefix(&attr::Struct {
+        prefix: enum_attr.prefix.clone(),
+        file: false,
+        rename_all: None,
+        strict_bool: false,
+    });
+    let enum_name_as_string: String = enum_name.to_string();
 
-    Ok(quote! {
-        impl Desenv for #struct_name {
-            fn _load(parent_prefix: Option<String>) -> Result<Self, ::desenv::Error>
+    let load_token: TokenStream = quote! {
+        let tag_var: String = format!("{}{}", #current_prefix.unwrap_or_default(), #tag);
+        let tag: String = source.var(tag_var.as_str()).map_err(|err| match err {
+            std::env::VarError::NotPresent => ::desenv::Error::MissingVar(tag_var.clone()),
+            std::env::VarError::NotUnicode(_) => ::desenv::Error::NotUnicodeVar(tag_var.clone()),
+        })?;
+
+        match tag.as_str() {
+            #(#arms,)*
+            _ => Err(::desenv::Error::ParseFromStr(::desenv::ParseError::new(
+                &tag_var,
+                parent_path.as_deref().unwrap_or(#enum_name_as_string),
+                &tag,
+                ::desenv::VariantError::new(&tag, &[#(#expected),*]),
+            ))),
+        }
+    };
+
+    // The defaults of every variant are checked, whatever the tag.
+    let check_token: TokenStream = quote! {{
+        let mut errors: Vec<::desenv::Error> = vec![];
+        #(errors.extend(#checks);)*
+        errors
+    }};
+
+    Ok(impl_desenv(enum_name, &load_token, &check_token))
+}
+
+fn impl_desenv(name: &Ident, load_token: &TokenStream, check_token: &TokenStream) -> TokenStream {
+    quote! {
+        #[allow(clippy::needless_question_mark)]
+        impl Desenv for #name {
+            fn _load(
+                parent_prefix: Option<String>,
+                parent_path: Option<String>,
+                source: &dyn ::desenv::Source,
+            ) -> Result<Self, ::desenv::Error>
             where
                 Self: Sized,
             {
-                Ok(Self {
-                    #(#expanded_fields,)*
-                })
+                #load_token
+            }
+
+            fn _check_defaults(parent_prefix: Option<String>, parent_path: Option<String>) -> Vec<::desenv::Error>
+            where
+                Self: Sized,
+            {
+                #check_token
             }
         }
-    })
+    }
 }
 
-fn expand_fields(
+// Load all the fields and build the value using the given `constructor` (ex. `Self` or `Self::Variant`).
+// Every field is loaded even if a previous one failed, so that all the errors are reported at once.
+fn load_fields(
+    constructor: &TokenStream,
+    root_name: &Ident,
     struct_attr: &attr::Struct,
     fields: &Punctuated<syn::Field, Comma>,
-) -> Result<Vec<TokenStream>, Error> {
+) -> Result<TokenStream, Error> {
     let mut expanded_fields: Vec<TokenStream> = vec![];
+    let mut field_attrs: Vec<attr::Field> = vec![];
+
+    for field in fields {
+        let field_attr: attr::Field = attr::Field::from_attrs(&field.attrs, field.span())?;
+        expanded_fields.push(expand_field(root_name, field, &field_attr, struct_attr)?);
+        field_attrs.push(field_attr);
+    }
+
+    let field_idents: Vec<&Option<Ident>> = fields.iter().map(|field| &field.ident).collect();
+    let results: Vec<Ident> = (0..fields.len())
+        .map(|index| format_ident!("field_{}", index))
+        .collect();
+    let constraints: Vec<TokenStream> = constraints(root_name, struct_attr, fields, &field_attrs, &results)?;
+
+    if constraints.is_empty() {
+        return Ok(quote! {
+            match (#(#expanded_fields,)*) {
+                (#(Ok(#results),)*) => Ok(#constructor {
+                    #(#field_idents: #results,)*
+                }),
+                #[allow(unreachable_patterns)]
+                (#(#results,)*) => Err(::desenv::Error::merge(vec![#(#results.err(),)*])),
+            }
+        });
+    }
+
+    // The constraints between fields are checked once all the fields are resolved, and their errors are
+    // reported along with the ones of the fields.
+    Ok(quote! {{
+        let (#(#results,)*) = (#(#expanded_fields,)*);
+        let constraint_errors: Vec<Option<::desenv::Error>> = vec![#(#constraints,)*];
+        match (#(#results,)*) {
+            (#(Ok(#results),)*) if constraint_errors.iter().all(Option::is_none) => Ok(#constructor {
+                #(#field_idents: #results,)*
+            }),
+            (#(#results,)*) => Err(::desenv::Error::merge(
+                vec![#(#results.err(),)*].into_iter().chain(constraint_errors).collect(),
+            )),
+        }
+    }})
+}
+
+// Returns the first name set among the variable and its aliases in the quoted code. A warning is raised for
+// deprecated aliases if the `path` of the field is given.
+fn var_alias(
+    var_name: &TokenStream,
+    current_prefix: &TokenStream,
+    field_attr: &attr::Field,
+    file: bool,
+    path: Option<&TokenStream>,
+) -> TokenStream {
+    if field_attr.aliases.is_empty() {
+        return var_name.clone();
+    }
+
+    let names: Vec<&String> = field_attr.aliases.iter().map(|alias| &alias.name).collect();
+    let deprecated: Vec<bool> = field_attr.aliases.iter().map(|alias| alias.deprecated).collect();
+    let path_token: TokenStream = path.map_or_else(|| quote!(None), |path| quote!(Some(#path.as_str())));
+
+    quote! {
+        ::desenv::var_alias(
+            source,
+            #var_name,
+            &[#((format!("{}{}", #current_prefix.unwrap_or_default(), #names), #deprecated)),*],
+            #file,
+            #path_token,
+        )
+    }
+}
+
+// Returns whether the field is set in the quoted code: either its variable (or its `_FILE` variant if `file` is
+// set) is present, or its loaded `result` holds a value, which may come from a default. Optional fields hold a
+// value only if they are `Some`.
+fn is_set(var_name: &TokenStream, file: bool, result: &Ident, ty: Type) -> TokenStream {
+    let var_is_set: TokenStream = if file {
+        quote!(source.var_os(&#var_name).is_some() || source.var_os(&format!("{}_FILE", #var_name)).is_some())
+    } else {
+        quote!(source.var_os(&#var_name).is_some())
+    };
+
+    if ty == Type::Option {
+        quote!((#var_is_set || matches!(#result, Ok(Some(_)))))
+    } else {
+        quote!((#var_is_set || #result.is_ok()))
+    }
+}
+
+// Returns the value of the field compared by `required_if` in the quoted code, as an `Option<String>`: the value
+// of its variable or, when missing, its `default` string. Typed defaults are not compared.
+fn constraint_value(var_name: &TokenStream, file: bool, field_attr: &attr::Field) -> TokenStream {
+    let var_token: TokenStream = if file {
+        quote!(::desenv::var_or_file(source, &#var_name).ok().and_then(Result::ok))
+    } else {
+        quote!(source.var(&#var_name).ok())
+    };
+
+    match &field_attr.default {
+        Some(attr::Default::Value(value)) => quote!(#var_token.or_else(|| Some(#value.to_string()))),
+        Some(attr::Default::Env { vars, value }) => {
+            let value_token: TokenStream = value
+                .as_ref()
+                .map_or_else(|| quote!(None), |value| quote!(Some(#value.to_string())));
+            quote! {
+                #var_token
+                    #(.or_else(|| ::desenv::fallback_var(source, #vars).ok()))*
+                    .or_else(|| #value_token)
+            }
+        }
+        Some(attr::Default::Std | attr::Default::Fn(_) | attr::Default::Expr(_)) | None => var_token,
+    }
+}
+
+// Returns the `requires`, `conflicts_with` and `required_if` checks of the fields. Every check is quoted as an
+// `Option<desenv::Error>`, looking up the involved variables in the source and the loaded `results` of the fields,
+// so that the defaults are taken into account.
+fn constraints(
+    root_name: &Ident,
+    struct_attr: &attr::Struct,
+    fields: &Punctuated<syn::Field, Comma>,
+    field_attrs: &[attr::Field],
+    results: &[Ident],
+) -> Result<Vec<TokenStream>, Error> {
+    let current_prefix: TokenStream = current_prefix(struct_attr);
+    let field_name =
+        |field: &syn::Field| -> String { field.ident.as_ref().map(ToString::to_string).unwrap_or_default() };
+
+    // Returns the variable name of the field named `name`, referenced by `field`, whether it is set and its
+    // compared value, all quoted.
+    let target = |field: &syn::Field, name: &str| -> Result<(TokenStream, TokenStream, TokenStream), Error> {
+        match fields
+            .iter()
+            .zip(field_attrs)
+            .zip(results)
+            .find(|((target, _), _)| field_name(target) == name)
+        {
+            Some(((_, target_attr), _)) if target_attr.nested.is_some() => Err(Error::new(
+                field.span(),
+                format!("field `{name}` is marked as `nested` and cannot be used in constraints"),
+            )),
+            Some(((target, _), _)) if field_name(target) == field_name(field) => Err(Error::new(
+                field.span(),
+                "a field cannot be used in its own constraints",
+            )),
+            Some(((target, target_attr), result)) => {
+                let file: bool = target_attr.file || struct_attr.file;
+                let ty: Type = target_attr.kind.unwrap_or_else(|| Type::from_field(target));
+                let var: TokenStream = var_name(
+                    &field_name(target),
+                    &current_prefix,
+                    target_attr,
+                    struct_attr.rename_all,
+                );
+                let var: TokenStream = var_alias(&var, &current_prefix, target_attr, file, None);
+                let is_set_token: TokenStream = is_set(&var, file, result, ty);
+                let value_token: TokenStream = constraint_value(&var, file, target_attr);
+                Ok((var, is_set_token, value_token))
+            }
+            None => Err(Error::new(
+                field.span(),
+                format!("unknown field `{name}` in constraints"),
+            )),
+        }
+    };
+
+    let mut constraints: Vec<TokenStream> = vec![];
+
+    for ((field, field_attr), result) in fields.iter().zip(field_attrs).zip(results) {
+        let has_constraints: bool = !field_attr.requires.is_empty()
+            || !field_attr.conflicts_with.is_empty()
+            || !field_attr.required_if.is_empty();
+        if !has_constraints {
+            continue;
+        }
+        if field_attr.nested.is_some() {
+            return Err(Error::new(
+                field.span(),
+                "cannot set `requires`, `conflicts_with` or `required_if` modifiers on a field marked as `nested`",
+            ));
+        }
+
+        let file: bool = field_attr.file || struct_attr.file;
+        let ty: Type = field_attr.kind.unwrap_or_else(|| Type::from_field(field));
+        let var: TokenStream = var_name(&field_name(field), &current_prefix, field_attr, struct_attr.rename_all);
+        let var: TokenStream = var_alias(&var, &current_prefix, field_attr, file, None);
+        let path: TokenStream = field_path(root_name, &field_name(field));
+        let var_is_set: TokenStream = is_set(&var, file, result, ty);
+        let error = |rule: TokenStream| -> TokenStream {
+            quote!(Some(::desenv::Error::Validation(::desenv::ValidationError::new(&#var, &#path, &#rule))))
+        };
+
+        for name in &field_attr.requires {
+            let (other, other_is_set, _): (TokenStream, TokenStream, TokenStream) = target(field, name)?;
+            let error_token: TokenStream = error(quote!(format!("requires `{}` to be set", #other)));
+            constraints.push(quote! {
+                if #var_is_set && !#other_is_set {
+                    #error_token
+                } else {
+                    None
+                }
+            });
+        }
+
+        for name in &field_attr.conflicts_with {
+            let (other, other_is_set, _): (TokenStream, TokenStream, TokenStream) = target(field, name)?;
+            let error_token: TokenStream = error(quote!(format!("conflicts with `{}`", #other)));
+            constraints.push(quote! {
+                if #var_is_set && #other_is_set {
+                    #error_token
+                } else {
+                    None
+                }
+            });
+        }
+
+        for (name, value) in &field_attr.required_if {
+            let (other, _, other_value): (TokenStream, TokenStream, TokenStream) = target(field, name)?;
+            let error_token: TokenStream = error(quote!(format!("is required when `{}` is `{}`", #other, #value)));
+            constraints.push(quote! {
+                if !#var_is_set && #other_value.as_deref() == Some(#value) {
+                    #error_token
+                } else {
+                    None
+                }
+            });
+        }
+    }
+
+    Ok(constraints)
+}
+
+// Load all the fields from an empty source, so that every field falls back to its default, returning the errors.
+// Nested structs are checked with their own `_check_defaults`, so that the variants of nested enums are checked
+// too.
+fn check_fields(
+    root_name: &Ident,
+    struct_attr: &attr::Struct,
+    fields: &Punctuated<syn::Field, Comma>,
+) -> Result<TokenStream, Error> {
+    let current_prefix: TokenStream = current_prefix(struct_attr);
+    let mut checks: Vec<TokenStream> = vec![];
 
     for field in fields {
         let field_attr: attr::Field = attr::Field::from_attrs(&field.attrs, field.span())?;
-        expanded_fields.push(expand_field(field, &field_attr, struct_attr)?);
+        let field_name: String = field.ident.as_ref().map(ToString::to_string).unwrap_or_default();
+        let path: TokenStream = field_path(root_name, &field_name);
+        let ty: Type = field_attr.kind.unwrap_or_else(|| Type::from_field(field));
+
+        let field_type: &syn::Type = &field.ty;
+        let check_token: TokenStream = match (field_attr.nested.is_some(), ty) {
+            (true, Type::Vector) => {
+                let element_prefix: String = element_prefix(&field_name, &field_attr, struct_attr);
+                quote! {{
+                    let prefix: String = format!("{}{}", #current_prefix.unwrap_or_default(), #element_prefix);
+                    ::desenv::check_nested_vec(&prefix, &#path, std::marker::PhantomData::<#field_type>)
+                }}
+            }
+            (true, Type::Option) => {
+                let nested_prefix: TokenStream = nested_prefix(current_prefix.clone(), &field_attr);
+                quote!(::desenv::check_nested_option(#nested_prefix, #path, std::marker::PhantomData::<#field_type>))
+            }
+            (true, _) => {
+                let nested_prefix: TokenStream = nested_prefix(current_prefix.clone(), &field_attr);
+                quote!(<#field_type>::_check_defaults(#nested_prefix, Some(#path)))
+            }
+            (false, _) => {
+                let field_token: TokenStream = expand_field(root_name, field, &field_attr, struct_attr)?;
+                quote!(#field_token.err())
+            }
+        };
+        checks.push(check_token);
     }
 
-    Ok(expanded_fields)
+    Ok(quote! {{
+        let source: &dyn ::desenv::Source = &std::collections::HashMap::<String, String>::new();
+        let mut errors: Vec<::desenv::Error> = vec![];
+        #(errors.extend(#checks);)*
+        errors
+    }})
 }
 
 fn expand_field(
+    struct_name: &Ident,
     field: &syn::Field,
     field_attr: &attr::Field,
     struct_attr: &attr::Struct,
 ) -> Result<TokenStream, Error> {
-    let field_ident: &Option<Ident> = &field.ident;
-    let ty: Type = Type::from_field(field);
+    let field_type: &syn::Type = &field.ty;
+    let ty: Type = field_attr.kind.unwrap_or_else(|| Type::from_field(field));
+
+    // `Duration` does not implement `FromStr` and booleans are parsed leniently: both use the parsers shipped by
+    // the library. They are recognized by the field type, whatever the `kind` modifier.
+    let mut field_attr: attr::Field = field_attr.clone();
+    if field_attr.parse_with.is_none() {
+        if ty::is_duration(field) {
+            field_attr.parse_with = Some(syn::parse_quote!(::desenv::units::duration));
+        } else if ty::is_bool(field) && !field_attr.strict_bool && !struct_attr.strict_bool {
+            field_attr.parse_with = Some(syn::parse_quote!(::desenv::parse_bool));
+        }
+    }
+    field_attr.secret |= ty::is_secret(field);
+    let field_attr: &attr::Field = &field_attr;
     let current_prefix: TokenStream = current_prefix(struct_attr);
+    let field_identity_as_string: String = field
+        .ident
+        .as_ref()
+        .map(ToString::to_string)
+        .ok_or_else(|| Error::new(field.span(), "failed to stringify identity"))?;
+    let path: TokenStream = field_path(struct_name, field_identity_as_string.as_str());
 
-    if field_attr.nested {
-        let field_type: &syn::Type = &field.ty;
-        Ok(quote!(#field_ident: <#field_type>::_load(#current_prefix)?))
+    let token_stream: TokenStream = if field_attr.nested.is_some() && ty == Type::Vector {
+        // Elements are loaded from indexed prefixes (ex. `FIELD_0_`), the field name being replaced by the
+        // `nested.prefix` modifier if set.
+        let element_prefix: String = element_prefix(&field_identity_as_string, field_attr, struct_attr);
+        quote! {{
+            let prefix: String = format!("{}{}", #current_prefix.unwrap_or_default(), #element_prefix);
+            ::desenv::nested_vec(&prefix, &#path, source)?
+        }}
+    } else if field_attr.nested.is_some() {
+        let nested_prefix: TokenStream = nested_prefix(current_prefix, field_attr);
+        if ty == Type::Option {
+            quote!(::desenv::nested_option(#nested_prefix, #path, source)?)
+        } else {
+            quote!(<#field_type>::_load(#nested_prefix, Some(#path), source)?)
+        }
     } else if field_attr.separator.is_some() && ty != Type::Vector {
-        Err(Error::new(
+        return Err(Error::new(
             field.span(),
             "`separator` modifier can be used on vector only",
-        ))
+        ));
+    } else if field_attr.map.is_some() && ty != Type::Map {
+        return Err(Error::new(field.span(), "`map` modifier can be used on map only"));
+    } else if ty == Type::Map
+        && !field_attr.map.map_or(false, |map| map.inline)
+        && matches!(
+            field_attr.default,
+            Some(attr::Default::Value(_) | attr::Default::Env { .. })
+        )
+    {
+        return Err(Error::new(
+            field.span(),
+            "`default` with value or env modifier can be used on map fields only with `map(inline)`",
+        ));
+    } else if ty == Type::Map
+        && !field_attr.map.map_or(false, |map| map.inline)
+        && matches!(field_attr.default, Some(attr::Default::Fn(_) | attr::Default::Expr(_)))
+    {
+        return Err(Error::new(
+            field.span(),
+            "`default_fn` and `default_expr` modifiers can be used on map fields only with `map(inline)`",
+        ));
+    } else if ty == Type::Map && !field_attr.map.map_or(false, |map| map.inline) && !field_attr.aliases.is_empty() {
+        return Err(Error::new(
+            field.span(),
+            "`alias` and `deprecated_alias` modifiers can be used on map fields only with `map(inline)`",
+        ));
+    } else {
+        let var_name: TokenStream = var_name(
+            field_identity_as_string.as_str(),
+            &current_prefix,
+            field_attr,
+            struct_attr.rename_all,
+        );
+        let file: bool = field_attr.file || struct_attr.file;
+        let os_str: bool = field_attr.kind.is_none() && field_attr.parse_with.is_none() && ty::is_os_str(field);
+
+        if field_attr.aliases.is_empty() {
+            let field_token: TokenStream = quote_field(ty, os_str, &var_name, &path, field_attr, file);
+            validate(field_token, field_type, ty, &var_name, &path, field_attr)
+        } else {
+            // The variable is looked up once, the first name set among the primary one and the aliases being used
+            // for the rest of the field.
+            let alias_token: TokenStream = var_alias(&var_name, &current_prefix, field_attr, file, Some(&path));
+            let field_token: TokenStream = quote_field(ty, os_str, &quote!(var_name.clone()), &path, field_attr, file);
+            let validate_token: TokenStream = validate(
+                field_token,
+                field_type,
+                ty,
+                &quote!(var_name.clone()),
+                &path,
+                field_attr,
+            );
+            quote! {{
+                let var_name: String = #alias_token;
+                #validate_token
+            }}
+        }
+    };
+
+    // The errors of secret fields must not leak the raw value, neither in the error nor in its source.
+    let conceal_token: TokenStream = if field_attr.secret {
+        quote!(.map_err(::desenv::Error::conceal))
     } else {
-        let field_identity_as_string: String = field
-            .ident
-            .as_ref()
-            .map(ToString::to_string)
-            .ok_or_else(|| Error::new(field.span(), "failed to stringify identity"))?;
+        quote!()
+    };
 
-        let var_name: TokenStream = var_name(field_identity_as_string.as_str(), &current_prefix, field_attr);
-        let token_stream: TokenStream = quote_field(&ty, &var_name, field_attr);
+    // Each field is loaded in its own closure to catch both `?` and early `return`s.
+    Ok(quote!((|| -> Result<#field_type, ::desenv::Error> { Ok(#token_stream) })() #conceal_token))
+}
 
-        Ok(quote!(#field_ident: #token_stream))
+// Returns the prefix of the elements of a nested vector, followed by their index (ex. `FIELD_` for `FIELD_0_`).
+// The `rename_all` rule is applied to the field name only, the index is always surrounded by underscores.
+fn element_prefix(field_name: &str, field_attr: &attr::Field, struct_attr: &attr::Struct) -> String {
+    match (field_attr.nested_prefix(), struct_attr.rename_all) {
+        (Some(prefix), _) => prefix.to_string(),
+        (None, Some(rule)) => format!("{}_", rule.apply(field_name)),
+        (None, None) => format!("{}_", field_name.to_uppercase()),
     }
 }
 
-fn quote_field(ty: &Type, var_name: &TokenStream, field_attr: &attr::Field) -> TokenStream {
+// Returns the prefix of a nested struct, appending the `nested.prefix` modifier to the current one, in the quoted
+// code.
+fn nested_prefix(current_prefix: TokenStream, field_attr: &attr::Field) -> TokenStream {
+    match field_attr.nested_prefix() {
+        Some(prefix) => quote!(#current_prefix.map(|prefix| format!("{}{}", prefix, #prefix))),
+        None => current_prefix,
+    }
+}
+
+fn quote_field(
+    ty: Type,
+    os_str: bool,
+    var_name: &TokenStream,
+    path: &TokenStream,
+    field_attr: &attr::Field,
+    file: bool,
+) -> TokenStream {
+    let lookup: TokenStream = lookup(var_name, file);
+
     match ty {
-        Type::OsString => var_os(var_name),
-        Type::Option => var_opt(var_name, field_attr),
-        Type::Vector => var_vec(var_name, field_attr),
-        Type::Other => var(var_name, field_attr),
+        Type::OsString if field_attr.parse_with.is_none() => var_os(var_name, field_attr, file, false),
+        Type::Option | Type::Vector if os_str => var_os(var_name, field_attr, file, ty == Type::Vector),
+        Type::Option => var_opt(var_name, &lookup, path, field_attr),
+        Type::Vector => var_vec(var_name, &lookup, path, field_attr),
+        Type::Map => var_map(var_name, &lookup, path, field_attr),
+        Type::OsString | Type::Other => var(var_name, &lookup, path, field_attr),
     }
 }
 
-fn var(var_name: &TokenStream, field_attr: &attr::Field) -> TokenStream {
-    let parse_token: TokenStream = parse();
+// Check the rules of the `validate` and `validate_with` modifiers on the loaded value. The rules of optional
+// fields are checked only if the value is present.
+fn validate(
+    field_token: TokenStream,
+    field_type: &syn::Type,
+    ty: Type,
+    var_name: &TokenStream,
+    path: &TokenStream,
+    field_attr: &attr::Field,
+) -> TokenStream {
+    let quote_bound =
+        |bound: &Option<usize>| -> TokenStream { bound.map_or_else(|| quote!(None), |bound| quote!(Some(#bound))) };
 
+    let checks: Vec<TokenStream> = field_attr
+        .validate
+        .iter()
+        .map(|rule| match rule {
+            attr::Rule::Range { min, max } => {
+                let min: TokenStream = min.as_ref().map_or_else(|| quote!(None), |min| quote!(Some(#min)));
+                let max: TokenStream = max.as_ref().map_or_else(|| quote!(None), |max| quote!(Some(#max)));
+                quote!(::desenv::validate::range(value, #min, #max))
+            }
+            attr::Rule::Len { min, max } => {
+                let (min, max): (TokenStream, TokenStream) = (quote_bound(min), quote_bound(max));
+                quote!(::desenv::validate::len(value.len(), #min, #max))
+            }
+            attr::Rule::NonEmpty => quote!(::desenv::validate::non_empty(value.is_empty())),
+            attr::Rule::OneOf(values) => quote!(::desenv::validate::one_of(value, &[#(#values),*])),
+            attr::Rule::Regex(pattern) => quote! {({
+                static REGEX: ::desenv::validate::Regex = ::desenv::validate::Regex::new(#pattern);
+                ::desenv::validate::regex(AsRef::<str>::as_ref(value), &REGEX)
+            })},
+        })
+        .chain(field_attr.validate_with.iter().map(|function| {
+            // The custom rules of secret fields may describe the raw value (ex. `3 is not even`).
+            if field_attr.secret {
+                quote!(::desenv::validate::with_concealed(#function(value)))
+            } else {
+                quote!(::desenv::validate::with(#function(value)))
+            }
+        }))
+        .map(|check| {
+            quote! {
+                #check.map_err(|rule| {
+                    ::desenv::Error::Validation(::desenv::ValidationError::new(&#var_name, &#path, &rule))
+                })?;
+            }
+        })
+        .collect();
+
+    if checks.is_empty() {
+        return field_token;
+    }
+
+    let checks_token: TokenStream = if ty == Type::Option {
+        quote! {
+            if let Some(value) = value.as_ref() {
+                #(#checks)*
+            }
+        }
+    } else {
+        quote! {{
+            let value: &#field_type = &value;
+            #(#checks)*
+        }}
+    };
+
+    quote! {{
+        let value: #field_type = #field_token;
+        #checks_token
+        value
+    }}
+}
+
+fn var(var_name: &TokenStream, lookup: &TokenStream, path: &TokenStream, field_attr: &attr::Field) -> TokenStream {
     match &field_attr.default {
-        Some(attr::Default::Std) => quote! {
-            std::env::var(#var_name.as_str()).unwrap_or_default() #parse_token?
-        },
-        Some(attr::Default::Value(value)) => quote! {
-            std::env::var(#var_name.as_str()).unwrap_or(#value.to_string()) #parse_token?
-        },
-        Some(attr::Default::Env(env_var)) => {
+        Some(default @ (attr::Default::Fn(_) | attr::Default::Expr(_))) => {
+            let parse_token: TokenStream = parse(&quote!(var), var_name, path, field_attr);
+            let default_token: TokenStream = typed_default(default);
+            quote! {
+                match #lookup {
+                    Ok(var) => #parse_token?,
+                    Err(std::env::VarError::NotPresent) => #default_token,
+                    Err(std::env::VarError::NotUnicode(_)) => return Err(::desenv::Error::NotUnicodeVar(#var_name)),
+                }
+            }
+        }
+        Some(attr::Default::Std) => {
+            let parse_token: TokenStream = parse(&quote!(var), var_name, path, field_attr);
+            quote! {{
+                let var: String = #lookup.unwrap_or_default();
+                #parse_token?
+            }}
+        }
+        Some(attr::Default::Value(value)) => {
+            let parse_token: TokenStream = parse(&quote!(var), var_name, path, field_attr);
+            quote! {{
+                let var: String = #lookup.unwrap_or(#value.to_string());
+                #parse_token?
+            }}
+        }
+        Some(attr::Default::Env { vars, value }) => {
             let map_err_token: TokenStream = map_err(var_name);
+            let parse_token: TokenStream = parse(&quote!(var), var_name, path, field_attr);
+            let last_token: TokenStream = value.as_ref().map_or_else(
+                || quote!(return Err(::desenv::Error::MissingVar(#var_name))),
+                |value| {
+                    quote! {{
+                        let var: String = #value.to_string();
+                        #parse_token?
+                    }}
+                },
+            );
+            let default_token: TokenStream = default_env(
+                vars,
+                |env_var: &String| -> TokenStream {
+                    let parse_env_token: TokenStream =
+                        parse(&quote!(var), &quote!(#env_var.to_string()), path, field_attr);
+                    quote! {{
+                        let var: String = ::desenv::fallback_var(source, #env_var) #map_err_token?;
+                        #parse_env_token?
+                    }}
+                },
+                last_token,
+            );
             quote! {
-                match std::env::var(#var_name.as_str()) {
-                    Ok(var) => var #parse_token?,
+                match #lookup {
+                    Ok(var) => #parse_token?,
                     Err(std::env::VarError::NotUnicode(_)) => return Err(::desenv::Error::NotUnicodeVar(#var_name)),
-                    Err(std::env::VarError::NotPresent) => std::env::var(#env_var) #map_err_token? #parse_token?,
+                    Err(std::env::VarError::NotPresent) => #default_token,
                 }
             }
         }
         None => {
             let map_err_token: TokenStream = map_err(var_name);
-            quote!(std::env::var(#var_name.as_str()) #map_err_token? #parse_token?)
+            let parse_token: TokenStream = parse(&quote!(var), var_name, path, field_attr);
+            quote! {{
+                let var: String = #lookup #map_err_token?;
+                #parse_token?
+            }}
         }
     }
 }
 
-fn var_os(var_name: &TokenStream) -> TokenStream {
-    quote!(std::env::var_os(#var_name.as_str()).ok_or(::desenv::Error::MissingVar(#var_name))?)
+// Load the field from the raw variable using `FromOsStr`. Vectors are split on the platform path separator
+// unless the `separator` modifier is set.
+fn var_os(var_name: &TokenStream, field_attr: &attr::Field, file: bool, vector: bool) -> TokenStream {
+    let from_os_str = |value: TokenStream| -> TokenStream {
+        field_attr.separator.filter(|_| vector).map_or_else(
+            || quote!(::desenv::FromOsStr::from_os_str(#value)),
+            |separator| {
+                quote! {
+                    ::desenv::split_os_str(#value, #separator)
+                        .iter()
+                        .map(|v| ::desenv::FromOsStr::from_os_str(v))
+                        .collect()
+                }
+            },
+        )
+    };
+
+    let missing_token: TokenStream = match &field_attr.default {
+        Some(attr::Default::Std) => quote!(Default::default()),
+        Some(attr::Default::Value(value)) => from_os_str(quote!(std::ffi::OsStr::new(#value))),
+        Some(default @ (attr::Default::Fn(_) | attr::Default::Expr(_))) => typed_default(default),
+        Some(attr::Default::Env { vars, value }) => {
+            let from_env_token: TokenStream = from_os_str(quote!(&var));
+            let last_token: TokenStream = value.as_ref().map_or_else(
+                || quote!(return Err(::desenv::Error::MissingVar(#var_name))),
+                |value| from_os_str(quote!(std::ffi::OsStr::new(#value))),
+            );
+            default_env(
+                vars,
+                |env_var: &String| -> TokenStream {
+                    quote! {{
+                        let var: std::ffi::OsString = source.fallback_var_os(#env_var).unwrap_or_default();
+                        #from_env_token
+                    }}
+                },
+                last_token,
+            )
+        }
+        None => quote!(::desenv::FromOsStr::missing().ok_or_else(|| ::desenv::Error::MissingVar(#var_name))?),
+    };
+
+    let not_present_token: TokenStream = if file {
+        let from_file_token: TokenStream = from_os_str(quote!(std::ffi::OsStr::new(&var)));
+        quote! {
+            match ::desenv::var_or_file(source, #var_name.as_str())? {
+                Ok(var) => #from_file_token,
+                Err(_) => #missing_token,
+            }
+        }
+    } else {
+        missing_token
+    };
+    let from_var_token: TokenStream = from_os_str(quote!(&var));
+
+    quote! {
+        match source.var_os(#var_name.as_str()) {
+            Some(var) => #from_var_token,
+            None => #not_present_token,
+        }
+    }
 }
 
-fn var_opt(var_name: &TokenStream, field_attr: &attr::Field) -> TokenStream {
-    let parse_token: TokenStream = parse();
+fn var_opt(var_name: &TokenStream, lookup: &TokenStream, path: &TokenStream, field_attr: &attr::Field) -> TokenStream {
+    let parse_token: TokenStream = parse(&quote!(var), var_name, path, field_attr);
 
     let default_token: TokenStream = match &field_attr.default {
         Some(attr::Default::Std) => quote!(Some(Default::default())),
-        Some(attr::Default::Value(value)) => quote!(Some(#value.to_string())),
-        Some(attr::Default::Env(env_var)) => quote!(std::env::var(#env_var).ok()),
+        Some(attr::Default::Value(value)) => {
+            let parse_default_token: TokenStream = parse(&quote!(#value), var_name, path, field_attr);
+            quote!(Some(#parse_default_token?))
+        }
+        Some(default @ (attr::Default::Fn(_) | attr::Default::Expr(_))) => typed_default(default),
+        Some(attr::Default::Env { vars, value }) => {
+            let last_token: TokenStream = value.as_ref().map_or_else(
+                || quote!(None),
+                |value| {
+                    let parse_default_token: TokenStream = parse(&quote!(#value), var_name, path, field_attr);
+                    quote!(Some(#parse_default_token?))
+                },
+            );
+            default_env(
+                vars,
+                |env_var: &String| -> TokenStream {
+                    let parse_env_token: TokenStream =
+                        parse(&quote!(var), &quote!(#env_var.to_string()), path, field_attr);
+                    quote! {
+                        match ::desenv::fallback_var(source, #env_var) {
+                            Ok(var) => Some(#parse_env_token?),
+                            Err(_) => None,
+                        }
+                    }
+                },
+                last_token,
+            )
+        }
         None => quote!(None),
     };
 
     quote! {
-        match std::env::var(#var_name.as_str()) {
-            Ok(var) => Some(var #parse_token?),
+        match #lookup {
+            Ok(var) => Some(#parse_token?),
             Err(std::env::VarError::NotPresent) => #default_token,
             Err(std::env::VarError::NotUnicode(_)) => return Err(::desenv::Error::NotUnicodeVar(#var_name)),
         }
     }
 }
 
-fn var_vec(var_name: &TokenStream, field_attr: &attr::Field) -> TokenStream {
+fn var_vec(var_name: &TokenStream, lookup: &TokenStream, path: &TokenStream, field_attr: &attr::Field) -> TokenStream {
     let separator: char = field_attr.separator.unwrap_or(DEFAULT_SEPARATOR);
-    let parse_token: TokenStream = parse();
 
-    let vec_map_token: TokenStream = quote! {
-        .split(#separator)
-        .into_iter()
-        .map(|v| v.trim())
-        .filter(|v| !v.is_empty())
-        .map(|v| v #parse_token)
-        .collect::<Result<Vec<_>, desenv::Error>>()?
+    let vec_map = |var_name: &TokenStream| -> TokenStream {
+        let parse_token: TokenStream = parse(&quote!(v), var_name, path, field_attr);
+        quote! {
+            .split(#separator)
+            .into_iter()
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+            .map(|v| #parse_token)
+            .collect::<Result<Vec<_>, ::desenv::Error>>()?
+        }
     };
+    let vec_map_token: TokenStream = vec_map(var_name);
 
     let token: TokenStream = match &field_attr.default {
-        Some(attr::Default::Std) => quote!(Some(Default::default())),
+        Some(attr::Default::Std) => quote!(Default::default()),
         Some(attr::Default::Value(value)) => quote!(#value #vec_map_token),
-        Some(attr::Default::Env(env_var)) => {
+        Some(default @ (attr::Default::Fn(_) | attr::Default::Expr(_))) => typed_default(default),
+        Some(attr::Default::Env { vars, value }) => {
             let map_err: TokenStream = map_err(var_name);
-            quote!(std::env::var(#env_var) #map_err? #vec_map_token)
+            let last_token: TokenStream = value.as_ref().map_or_else(
+                || quote!(return Err(::desenv::Error::MissingVar(#var_name))),
+                |value| quote!(#value #vec_map_token),
+            );
+            default_env(
+                vars,
+                |env_var: &String| -> TokenStream {
+                    let vec_map_env_token: TokenStream = vec_map(&quote!(#env_var.to_string()));
+                    quote!(::desenv::fallback_var(source, #env_var) #map_err? #vec_map_env_token)
+                },
+                last_token,
+            )
         }
         None => quote!(return Err(::desenv::Error::MissingVar(#var_name))),
     };
 
     quote! {
-        match std::env::var(#var_name.as_str()) {
+        match #lookup {
             Ok(var) => var #vec_map_token,
             Err(std::env::VarError::NotPresent) => #token,
             Err(std::env::VarError::NotUnicode(_)) => return Err(::desenv::Error::NotUnicodeVar(#var_name)),
@@ -167,6 +887,97 @@ fn var_vec(var_name: &TokenStream, field_attr: &attr::Field) -> TokenStream {
     }
 }
 
+fn var_map(var_name: &TokenStream, lookup: &TokenStream, path: &TokenStream, field_attr: &attr::Field) -> TokenStream {
+    let map: attr::Map = field_attr.map.unwrap_or_default();
+    let key_case_token: TokenStream = key_case(map.key_case);
+
+    if !map.inline {
+        // Collect all the variables named `<var_name>_<key>`.
+        let map_err_token: TokenStream = map_err(&quote!(var.clone()));
+        let parse_token: TokenStream = parse(&quote!(value), &quote!(var), path, field_attr);
+        return quote! {{
+            let prefix: String = format!("{}_", #var_name);
+            source
+                .var_names()
+                .into_iter()
+                .filter(|var| var.len() > prefix.len() && var.starts_with(prefix.as_str()))
+                .map(|var| -> Result<(String, _), ::desenv::Error> {
+                    let value: String = source.var(var.as_str()) #map_err_token?;
+                    Ok((var[prefix.len()..] #key_case_token, #parse_token?))
+                })
+                .collect::<Result<_, ::desenv::Error>>()?
+        }};
+    }
+
+    let separator: char = map.separator.unwrap_or(DEFAULT_SEPARATOR);
+    let key_separator: char = map.key_separator.unwrap_or(DEFAULT_KEY_SEPARATOR);
+
+    let map_map = |var_name: &TokenStream| -> TokenStream {
+        let parse_token: TokenStream = parse(&quote!(value), var_name, path, field_attr);
+        quote! {
+            .split(#separator)
+            .map(|pair| pair.trim())
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| -> Result<(String, _), ::desenv::Error> {
+                let (key, value) = pair.split_once(#key_separator).ok_or_else(|| {
+                    let message: String = format!("missing key/value separator `{}`", #key_separator);
+                    ::desenv::Error::ParseFromStr(::desenv::ParseError::new(&#var_name, &#path, pair, message))
+                })?;
+                let value: &str = value.trim();
+                Ok((key.trim() #key_case_token, #parse_token?))
+            })
+            .collect::<Result<_, ::desenv::Error>>()?
+        }
+    };
+    let map_map_token: TokenStream = map_map(var_name);
+
+    let token: TokenStream = match &field_attr.default {
+        Some(attr::Default::Std) => quote!(Default::default()),
+        Some(attr::Default::Value(value)) => quote!(#value #map_map_token),
+        Some(default @ (attr::Default::Fn(_) | attr::Default::Expr(_))) => typed_default(default),
+        Some(attr::Default::Env { vars, value }) => {
+            let map_err: TokenStream = map_err(var_name);
+            let last_token: TokenStream = value.as_ref().map_or_else(
+                || quote!(return Err(::desenv::Error::MissingVar(#var_name))),
+                |value| quote!(#value #map_map_token),
+            );
+            default_env(
+                vars,
+                |env_var: &String| -> TokenStream {
+                    let map_map_env_token: TokenStream = map_map(&quote!(#env_var.to_string()));
+                    quote!(::desenv::fallback_var(source, #env_var) #map_err? #map_map_env_token)
+                },
+                last_token,
+            )
+        }
+        None => quote!(return Err(::desenv::Error::MissingVar(#var_name))),
+    };
+
+    quote! {
+        match #lookup {
+            Ok(var) => var #map_map_token,
+            Err(std::env::VarError::NotPresent) => #token,
+            Err(std::env::VarError::NotUnicode(_)) => return Err(::desenv::Error::NotUnicodeVar(#var_name)),
+        }
+    }
+}
+
+// Turn a map key (a `&str`) into the `String` stored in the map, applying the `map.key_case` rule. Keys are
+// split in words on `_` and `-`.
+fn key_case(rule: Option<RenameRule>) -> TokenStream {
+    match rule {
+        None => quote!(.to_string()),
+        Some(RenameRule::Lower) => quote!(.to_lowercase().replace(['_', '-'], "")),
+        Some(RenameRule::Upper) => quote!(.to_uppercase().replace(['_', '-'], "")),
+        Some(RenameRule::Snake) => quote!(.to_lowercase().replace('-', "_")),
+        Some(RenameRule::ScreamingSnake) => quote!(.to_uppercase().replace('-', "_")),
+        Some(RenameRule::Kebab) => quote!(.to_lowercase().replace('_', "-")),
+        Some(RenameRule::ScreamingKebab) => quote!(.to_uppercase().replace('_', "-")),
+        // Rejected while parsing the `map.key_case` modifier.
+        Some(RenameRule::Pascal | RenameRule::Camel) => unreachable!(),
+    }
+}
+
 fn map_err(var_name: &TokenStream) -> TokenStream {
     quote!(.map_err(|err| match err {
         std::env::VarError::NotPresent => ::desenv::Error::MissingVar(#var_name),
@@ -174,17 +985,66 @@ fn map_err(var_name: &TokenStream) -> TokenStream {
     }))
 }
 
-fn parse() -> TokenStream {
-    quote!(.parse().map_err(|error| ::desenv::Error::ParseFromStr(format!("{:?}", error))))
+// Look up the fallback variables of `default(env = "...")` in order, quoting `found` for the first one set and
+// `last` if none of them is.
+fn default_env<F>(vars: &[String], found: F, last: TokenStream) -> TokenStream
+where
+    F: Fn(&String) -> TokenStream,
+{
+    vars.iter().rev().fold(last, |next, env_var| {
+        let found_token: TokenStream = found(env_var);
+        quote! {
+            if source.fallback_var_os(#env_var).is_some() {
+                #found_token
+            } else {
+                #next
+            }
+        }
+    })
+}
+
+// Returns the value of a `default_fn` or `default_expr` default in the quoted code. It has the type of the field,
+// so that a wrong default is a compile error.
+fn typed_default(default: &attr::Default) -> TokenStream {
+    match default {
+        attr::Default::Fn(function) => quote!(#function()),
+        attr::Default::Expr(expr) => quote!({ #expr }),
+        attr::Default::Std => quote!(Default::default()),
+        attr::Default::Value(_) | attr::Default::Env { .. } => unreachable!("value and env defaults are parsed"),
+    }
 }
 
-// Returns the environment variable name that should be fetched. If could be the field name upcased
-// or the rename value (both prefixed).
-fn var_name(field_name: &str, current_prefix: &TokenStream, field_attr: &attr::Field) -> TokenStream {
-    let var_name: String = if let attr::Field { rename: Some(rename), .. } = field_attr {
-        rename.to_string()
+// Look up the variable, falling back to the content of the file at `<var_name>_FILE` if `file` is set. The
+// quoted code returns a `Result<String, std::env::VarError>`.
+fn lookup(var_name: &TokenStream, file: bool) -> TokenStream {
+    if file {
+        quote!(::desenv::var_or_file(source, #var_name.as_str())?)
     } else {
-        field_name.to_uppercase()
+        quote!(source.var(#var_name.as_str()))
+    }
+}
+
+// Parse the given `value` expression (a string) to the field type, keeping track of the variable name and
+// of the field path in the resulting error. The `parse_with` function is used instead of `FromStr` if set.
+fn parse(value: &TokenStream, var_name: &TokenStream, path: &TokenStream, field_attr: &attr::Field) -> TokenStream {
+    field_attr.parse_with.as_ref().map_or_else(
+        || quote!(::desenv::ParseError::parse(&#value, &#var_name, &#path)),
+        |parser| quote!(::desenv::ParseError::parse_with(&#value, &#var_name, &#path, #parser)),
+    )
+}
+
+// Returns the environment variable name that should be fetched. If could be the field name upcased (or
+// cased using the `rename_all` rule) or the rename value (both prefixed).
+fn var_name(
+    field_name: &str,
+    current_prefix: &TokenStream,
+    field_attr: &attr::Field,
+    rename_all: Option<RenameRule>,
+) -> TokenStream {
+    let var_name: String = match (&field_attr.rename, rename_all) {
+        (Some(rename), _) => rename.to_string(),
+        (None, Some(rule)) => rule.apply(field_name),
+        (None, None) => field_name.to_uppercase(),
     };
 
     quote!(format!("{}{}", #current_prefix.unwrap_or_default(), #var_name))
@@ -195,3 +1055,9 @@ fn current_prefix(struct_attr: &attr::Struct) -> TokenStream {
     let prefix: String = struct_attr.get_prefix();
     quote!(parent_prefix.clone().map(|v| format!("{}{}", v, #prefix)).or_else(|| Some(#prefix.to_string())))
 }
+
+// Returns the path of the field starting from the root struct (ex. `Config.nested.field`) in the quoted code.
+fn field_path(struct_name: &Ident, field_name: &str) -> TokenStream {
+    let struct_name: String = struct_name.to_string();
+    quote!(format!("{}.{}", parent_path.as_deref().unwrap_or(#struct_name), #field_name))
+}
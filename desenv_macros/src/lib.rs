@@ -8,9 +8,11 @@ use crate::retainer::DeriveAttributeFilter;
 
 #[allow(dead_code)]
 mod attr;
+mod case;
 mod derive;
 mod retainer;
 mod ty;
+mod value;
 
 #[proc_macro_derive(Desenv, attributes(desenv))]
 pub fn derive_desenv(input: TokenStream) -> TokenStream {
@@ -37,9 +39,46 @@ pub fn derive_desenv(input: TokenStream) -> TokenStream {
                 "desenv could be derived only on structs with named fields",
             )),
         },
+        // Enums are loaded picking the variant using the `tag` variable
+        syn::Data::Enum(ref data_enum) => {
+            match attr::Enum::from_attrs(derive_input.attrs.as_slice(), derive_input.span()) {
+                Ok(attrs) => derive::desenv_enum(&derive_input.ident, &attrs, &data_enum.variants),
+                Err(err) => Err(err),
+            }
+        }
+        syn::Data::Union(_) => Err(Error::new(
+            derive_input_span,
+            "desenv could be derived only on non-tuple struct or enum types",
+        )),
+    };
+
+    match derivation_result {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(DesenvValue, attributes(desenv))]
+pub fn derive_desenv_value(input: TokenStream) -> TokenStream {
+    let mut derive_input: DeriveInput = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    // Remove all non-desenv attributes to avoid conflicting with other derive proc macro attributes.
+    derive_input.retain_attrs();
+
+    // Create a new span to get expansion information
+    let derive_input_span: Span = derive_input.span();
+
+    let derivation_result: Result<proc_macro2::TokenStream, Error> = match derive_input.data {
+        // Only enums with unit variants are allowed
+        syn::Data::Enum(ref data_enum) => {
+            match attr::Enum::from_attrs(derive_input.attrs.as_slice(), derive_input.span()) {
+                Ok(attrs) => value::desenv_value(&derive_input.ident, &attrs, &data_enum.variants),
+                Err(err) => Err(err),
+            }
+        }
         _ => Err(Error::new(
             derive_input_span,
-            "desenv could be derived only on non-tuple struct types",
+            "desenv value could be derived only on enum types",
         )),
     };
 
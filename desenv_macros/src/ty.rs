@@ -1,20 +1,146 @@
-#[derive(Eq, PartialEq)]
+use syn::{GenericArgument, PathArguments};
+
+const OS_STR_TYPES: [&str; 2] = ["OsString", "PathBuf"];
+
+/// How a field is loaded, depending on its type.
+#[derive(Clone, Copy, Eq, PartialEq)]
 pub enum Type {
     OsString,
     Option,
     Vector,
+    Map,
     Other,
 }
 
 impl Type {
+    pub const USAGE: &'static str = "\"plain\", \"option\", \"vec\", \"map\" or \"os_string\"";
+
+    /// Returns the kind set with the `kind` field modifier, used when the macro cannot see through the field
+    /// type (ex. type aliases).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "plain" => Some(Self::Other),
+            "option" => Some(Self::Option),
+            "vec" => Some(Self::Vector),
+            "map" => Some(Self::Map),
+            "os_string" => Some(Self::OsString),
+            _ => None,
+        }
+    }
+
+    /// Classify the field looking at the last segment of its type path, so that both `Vec<T>` and
+    /// `std::vec::Vec<T>` are recognized.
     pub fn from_field(field: &syn::Field) -> Self {
-        let ty: &syn::Type = &field.ty;
+        let (ident, arguments): (String, Vec<&syn::Type>) = match last_segment(&field.ty) {
+            Some(segment) => segment,
+            None => return Self::Other,
+        };
 
-        match quote::quote!(#ty).to_string().as_str() {
-            "OsString" => Self::OsString,
-            s if s.starts_with("Option <") => Self::Option,
-            s if s.starts_with("Vec < ") => Self::Vector,
+        match (ident.as_str(), arguments.len()) {
+            (ident, 0) if OS_STR_TYPES.contains(&ident) => Self::OsString,
+            ("Option", 1) => Self::Option,
+            ("Vec", 1) => Self::Vector,
+            ("HashMap", 2 | 3) | ("BTreeMap", 2) => Self::Map,
             _ => Self::Other,
         }
     }
 }
+
+/// Returns whether the field is loaded from the raw variable, without requiring it to be valid unicode. It's
+/// the case of `OsString`, `PathBuf` and of optional values or vectors of them.
+pub fn is_os_str(field: &syn::Field) -> bool {
+    let is_os_str_type = |ty: &syn::Type| -> bool {
+        matches!(
+            last_segment(ty),
+            Some((ident, arguments)) if arguments.is_empty() && OS_STR_TYPES.contains(&ident.as_str())
+        )
+    };
+
+    element_is(&field.ty, is_os_str_type, false)
+}
+
+/// Returns whether the field is a `Duration`, or an optional value, a vector or a map of durations. These
+/// fields are parsed using `desenv::units::duration` since `Duration` does not implement `FromStr`.
+///
+/// Only a bare `Duration` path or a path ending in `time::Duration` is recognized, so that other types sharing
+/// the name (ex. `chrono::Duration`) keep being parsed with `FromStr`.
+pub fn is_duration(field: &syn::Field) -> bool {
+    let is_duration_type = |ty: &syn::Type| -> bool {
+        let segments: Vec<&syn::PathSegment> = match ty {
+            syn::Type::Path(type_path) if type_path.qself.is_none() => type_path.path.segments.iter().collect(),
+            _ => return false,
+        };
+
+        let is_duration_segment =
+            |segment: &syn::PathSegment| -> bool { segment.ident == "Duration" && segment.arguments.is_empty() };
+        match segments.as_slice() {
+            [last] => is_duration_segment(last),
+            [.., parent, last] => parent.ident == "time" && is_duration_segment(last),
+            [] => false,
+        }
+    };
+
+    element_is(&field.ty, is_duration_type, true)
+}
+
+/// Returns whether the field is a `bool`, or an optional value, a vector or a map of booleans. These fields are
+/// parsed using `desenv::parse_bool` unless the `strict_bool` modifier is set.
+pub fn is_bool(field: &syn::Field) -> bool {
+    let is_bool_type = |ty: &syn::Type| -> bool {
+        matches!(last_segment(ty), Some((ident, arguments)) if ident == "bool" && arguments.is_empty())
+    };
+
+    element_is(&field.ty, is_bool_type, true)
+}
+
+/// Returns whether the field is a `Secret`, or an optional value, a vector or a map of secrets. The errors of
+/// these fields never contain the raw value.
+pub fn is_secret(field: &syn::Field) -> bool {
+    let is_secret_type = |ty: &syn::Type| -> bool { matches!(last_segment(ty), Some((ident, _)) if ident == "Secret") };
+
+    match last_segment(&field.ty) {
+        Some((ident, arguments)) if ["Option", "Vec", "HashMap", "BTreeMap"].contains(&ident.as_str()) => {
+            arguments.into_iter().any(is_secret_type)
+        }
+        _ => is_secret_type(&field.ty),
+    }
+}
+
+// Returns whether `ty`, or the type of its elements if it's an `Option`, a `Vec` or (if `maps` is set) a map,
+// matches `is_element`.
+fn element_is(ty: &syn::Type, is_element: impl Fn(&syn::Type) -> bool, maps: bool) -> bool {
+    match last_segment(ty) {
+        Some((ident, arguments)) if (ident == "Option" || ident == "Vec") && arguments.len() == 1 => {
+            is_element(arguments[0])
+        }
+        Some((ident, arguments)) if maps && (ident == "HashMap" || ident == "BTreeMap") && arguments.len() >= 2 => {
+            is_element(arguments[1])
+        }
+        _ => is_element(ty),
+    }
+}
+
+// Returns the name of the last segment of the type path with its generic type arguments.
+fn last_segment(ty: &syn::Type) -> Option<(String, Vec<&syn::Type>)> {
+    let segment: &syn::PathSegment = match ty {
+        syn::Type::Group(group) => return last_segment(&group.elem),
+        syn::Type::Paren(paren) => return last_segment(&paren.elem),
+        syn::Type::Path(type_path) if type_path.qself.is_none() => type_path.path.segments.last()?,
+        _ => return None,
+    };
+
+    let arguments: Vec<&syn::Type> = match &segment.arguments {
+        PathArguments::None => vec![],
+        PathArguments::AngleBracketed(arguments) => arguments
+            .args
+            .iter()
+            .filter_map(|argument| match argument {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        PathArguments::Parenthesized(_) => return None,
+    };
+
+    Some((segment.ident.to_string(), arguments))
+}
@@ -0,0 +1,82 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::token::Comma;
+use syn::{Error, Fields};
+
+use crate::attr;
+
+pub fn desenv_value(
+    enum_name: &Ident,
+    enum_attr: &attr::Enum,
+    variants: &Punctuated<syn::Variant, Comma>,
+) -> Result<TokenStream, Error> {
+    let mut arms: Vec<TokenStream> = vec![];
+    let mut expected: Vec<String> = vec![];
+
+    if enum_attr.tag.is_some() || enum_attr.prefix.is_some() || enum_attr.file {
+        return Err(Error::new(
+            enum_name.span(),
+            "`tag`, `prefix` and `file` modifiers can be used only deriving `Desenv`",
+        ));
+    }
+
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new(
+                variant.span(),
+                "desenv value could be derived only on enums with unit variants",
+            ));
+        }
+
+        let variant_attr: attr::Variant = attr::Variant::from_attrs(&variant.attrs, variant.span())?;
+        let variant_ident: &Ident = &variant.ident;
+
+        if variant_attr.prefix.is_some() {
+            return Err(Error::new(
+                variant.span(),
+                "`prefix` modifier can be used only deriving `Desenv`",
+            ));
+        }
+
+        let values: Vec<String> = variant_values(variant_ident, &variant_attr, enum_attr);
+
+        if let Some(value) = values.iter().find(|value| expected.contains(value)) {
+            return Err(Error::new(
+                variant.span(),
+                format!("value `{value}` is accepted by more than one variant"),
+            ));
+        }
+
+        arms.push(quote!(#(#values)|* => Ok(Self::#variant_ident)));
+        expected.extend(values);
+    }
+
+    Ok(quote! {
+        impl ::std::str::FromStr for #enum_name {
+            type Err = ::desenv::VariantError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                match value {
+                    #(#arms,)*
+                    _ => Err(::desenv::VariantError::new(value, &[#(#expected),*])),
+                }
+            }
+        }
+    })
+}
+
+// Returns the values matching the variant. The first one could be the variant name, cased using the
+// `rename_all` rule, or the rename value. The others are the aliases.
+pub fn variant_values(variant_ident: &Ident, variant_attr: &attr::Variant, enum_attr: &attr::Enum) -> Vec<String> {
+    let name: String = match (&variant_attr.rename, enum_attr.rename_all) {
+        (Some(rename), _) => rename.clone(),
+        (None, Some(rule)) => rule.apply(&variant_ident.to_string()),
+        (None, None) => variant_ident.to_string(),
+    };
+
+    std::iter::once(name)
+        .chain(variant_attr.aliases.iter().cloned())
+        .collect()
+}
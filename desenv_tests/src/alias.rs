@@ -0,0 +1,94 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use desenv::{Desenv, Error, Source, Warning};
+
+#[derive(Desenv, Debug)]
+#[desenv(prefix = "APP_")]
+pub struct Config {
+    #[desenv(alias = "LISTEN_PORT", deprecated_alias = "OLD_PORT")]
+    pub port: u16,
+    #[desenv(deprecated_alias = "HOSTNAME")]
+    pub host: Option<String>,
+    #[desenv(alias = "LEVEL", default = "info")]
+    pub log_level: String,
+}
+
+#[test]
+fn load_from_primary_name() {
+    let (config, warnings): (Config, Vec<Warning>) = desenv::load_from_with_warnings(&HashMap::from([
+        ("APP_PORT".to_string(), "80".to_string()),
+        ("APP_OLD_PORT".to_string(), "81".to_string()),
+    ]))
+    .unwrap();
+
+    assert_eq!(config.port, 80);
+    assert_eq!(config.host, None);
+    assert_eq!(config.log_level, "info");
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn load_from_aliases_in_order() {
+    let (config, warnings): (Config, Vec<Warning>) = desenv::load_from_with_warnings(&HashMap::from([
+        ("APP_LISTEN_PORT".to_string(), "80".to_string()),
+        ("APP_OLD_PORT".to_string(), "81".to_string()),
+        ("APP_LEVEL".to_string(), "debug".to_string()),
+    ]))
+    .unwrap();
+
+    assert_eq!(config.port, 80);
+    assert_eq!(config.log_level, "debug");
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn load_from_deprecated_alias_raises_warnings() {
+    let (config, warnings): (Config, Vec<Warning>) = desenv::load_from_with_warnings(&HashMap::from([
+        ("APP_OLD_PORT".to_string(), "81".to_string()),
+        ("APP_HOSTNAME".to_string(), "localhost".to_string()),
+    ]))
+    .unwrap();
+
+    assert_eq!(config.port, 81);
+    assert_eq!(config.host.as_deref(), Some("localhost"));
+    assert_eq!(
+        warnings.iter().map(ToString::to_string).collect::<Vec<String>>(),
+        vec![
+            "Deprecated env var `APP_OLD_PORT` for `Config.port`, use `APP_PORT` instead",
+            "Deprecated env var `APP_HOSTNAME` for `Config.host`, use `APP_HOST` instead",
+        ]
+    );
+}
+
+#[test]
+fn deprecated_alias_warnings_are_reported_to_hook() {
+    let reported: RefCell<Vec<Warning>> = RefCell::new(vec![]);
+    let source = HashMap::from([("APP_OLD_PORT".to_string(), "81".to_string())])
+        .on_warning(|warning: &Warning| reported.borrow_mut().push(warning.clone()));
+
+    let config: Config = desenv::load_from(&source).unwrap();
+
+    assert_eq!(config.port, 81);
+    assert_eq!(
+        reported.into_inner(),
+        vec![Warning::DeprecatedVar {
+            var: "APP_OLD_PORT".to_string(),
+            replacement: "APP_PORT".to_string(),
+            path: "Config.port".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn errors_name_the_alias_the_value_is_loaded_from() {
+    let config: Result<Config, Error> = desenv::load_from_iter([("APP_OLD_PORT", "not-a-port")]);
+
+    match config.unwrap_err() {
+        Error::ParseFromStr(error) => assert_eq!(error.var(), "APP_OLD_PORT"),
+        error => panic!("expected parse error, got {error}"),
+    }
+
+    let config: Result<Config, Error> = desenv::load_from_iter([("APP_HOST", "localhost")]);
+    assert_eq!(config.unwrap_err().to_string(), "Missing env var `APP_PORT`");
+}
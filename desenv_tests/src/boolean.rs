@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use desenv::{Desenv, Error};
+
+#[derive(Desenv, Debug)]
+pub struct Flags {
+    pub enabled: bool,
+    pub verbose: Option<bool>,
+    #[desenv(strict_bool)]
+    pub strict: Option<bool>,
+    #[desenv(default)]
+    pub stages: Vec<bool>,
+    #[desenv(map(inline), default)]
+    pub features: HashMap<String, bool>,
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(strict_bool)]
+pub struct StrictFlags {
+    pub enabled: bool,
+}
+
+#[test]
+fn parse_lenient_booleans() {
+    for value in ["true", "TRUE", "1", "yes", "Y", "on", " On "] {
+        assert!(desenv::parse_bool(value).unwrap(), "{}", value);
+    }
+    for value in ["false", "False", "0", "no", "N", "off", "OFF"] {
+        assert!(!desenv::parse_bool(value).unwrap(), "{}", value);
+    }
+    assert_eq!(
+        desenv::parse_bool("enabled").unwrap_err().to_string(),
+        "invalid boolean `enabled`, expected one of `true`, `1`, `yes`, `y`, `on` or `false`, `0`, `no`, `n`, `off`"
+    );
+}
+
+#[test]
+fn deserialize_lenient_booleans() {
+    let config: Flags = desenv::load_from_iter([("ENABLED", "yes"), ("VERBOSE", "0")]).unwrap();
+    assert!(config.enabled);
+    assert_eq!(config.verbose, Some(false));
+    assert_eq!(config.strict, None);
+    assert!(config.stages.is_empty());
+    assert!(config.features.is_empty());
+}
+
+#[test]
+fn deserialize_lenient_boolean_elements() {
+    let config: Flags = desenv::load_from_iter([
+        ("ENABLED", "on"),
+        ("STAGES", "yes, 0, off"),
+        ("FEATURES", "cache=y,trace=n"),
+    ])
+    .unwrap();
+    assert_eq!(config.stages, vec![true, false, false]);
+    assert_eq!(config.features.get("cache"), Some(&true));
+    assert_eq!(config.features.get("trace"), Some(&false));
+}
+
+#[test]
+fn deserialize_strict_boolean_field_fail() {
+    let config: Result<Flags, Error> = desenv::load_from_iter([("ENABLED", "on"), ("STRICT", "on")]);
+    assert_eq!(
+        config.unwrap_err().to_string(),
+        "Cannot parse env var `STRICT` into `Flags.strict`: provided string was not `true` or `false`"
+    );
+}
+
+#[test]
+fn deserialize_strict_boolean_struct() {
+    let config: StrictFlags = desenv::load_from_iter([("ENABLED", "true")]).unwrap();
+    assert!(config.enabled);
+
+    let config: Result<StrictFlags, Error> = desenv::load_from_iter([("ENABLED", "1")]);
+    assert_eq!(
+        config.unwrap_err().to_string(),
+        "Cannot parse env var `ENABLED` into `StrictFlags.enabled`: provided string was not `true` or `false`"
+    );
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use desenv::{Desenv, Error};
+
+#[derive(Desenv, Debug)]
+pub struct Valid {
+    #[desenv(default = "8080")]
+    pub port: u16,
+    #[desenv(default = "1,2,3", separator = ',')]
+    pub ids: Vec<u8>,
+    #[desenv(default = "30s")]
+    pub timeout: Duration,
+    #[desenv(default = "yes")]
+    pub debug: bool,
+    #[desenv(default = "a=1;b=2", map(inline, separator = ';'))]
+    pub weights: HashMap<String, u8>,
+    pub host: String,
+    #[desenv(default(env = "FALLBACK_NAME"))]
+    pub name: String,
+    #[desenv(nested)]
+    pub database: Database,
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(prefix = "DB_")]
+pub struct Database {
+    #[desenv(default = "5432")]
+    pub port: u16,
+}
+
+#[test]
+fn check_valid_defaults() {
+    desenv::check_defaults::<Valid>().unwrap();
+
+    let config: Valid = desenv::load_from_iter([("HOST", "localhost"), ("FALLBACK_NAME", "api")]).unwrap();
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.ids, vec![1, 2, 3]);
+    assert_eq!(config.timeout, Duration::from_secs(30));
+    assert!(config.debug);
+    assert_eq!(
+        config.weights,
+        HashMap::from([("a".to_string(), 1), ("b".to_string(), 2)])
+    );
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.name, "api");
+    assert_eq!(config.database.port, 5432);
+}
+
+pub type Databases = Vec<Database>;
+pub type MaybeDatabase = Option<Database>;
+
+#[derive(Desenv, Debug)]
+pub struct Aliased {
+    #[desenv(nested, kind = "vec")]
+    pub replicas: Databases,
+    #[desenv(nested(prefix = "BACKUP_"), kind = "option")]
+    pub backup: MaybeDatabase,
+}
+
+#[test]
+fn check_valid_defaults_with_aliased_nested_types() {
+    desenv::check_defaults::<Aliased>().unwrap();
+
+    let config: Aliased = desenv::load_from_iter([("REPLICAS_0_DB_PORT", "5433"), ("BACKUP_DB_PORT", "5434")]).unwrap();
+    assert_eq!(
+        config
+            .replicas
+            .iter()
+            .map(|database| database.port)
+            .collect::<Vec<u16>>(),
+        vec![5433]
+    );
+    assert_eq!(config.backup.map(|database| database.port), Some(5434));
+}
+
+#[derive(Desenv, Debug)]
+pub struct Invalid {
+    #[desenv(default = "abc")]
+    pub port: u16,
+    #[desenv(default = "1;2;x", separator = ';')]
+    pub ids: Vec<u8>,
+    #[desenv(default, validate(non_empty))]
+    pub name: String,
+    #[desenv(nested)]
+    pub nested: Vec<Mode>,
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(tag = "MODE")]
+pub enum Mode {
+    Local {
+        #[desenv(default = "many")]
+        count: usize,
+    },
+    Remote {
+        #[desenv(default = "3")]
+        retries: u8,
+    },
+}
+
+#[test]
+fn invalid_defaults_are_used_only_when_missing() {
+    let config: Invalid = desenv::load_from_iter([("PORT", "80"), ("IDS", "1"), ("NAME", "api")]).unwrap();
+    assert_eq!((config.port, config.ids, config.name), (80, vec![1], "api".to_string()));
+    assert!(config.nested.is_empty());
+
+    let mode: Mode = desenv::load_from_iter([("MODE", "Remote")]).unwrap();
+    assert!(matches!(mode, Mode::Remote { retries: 3 }));
+    assert!(desenv::load_from_iter::<Mode, _, _, _>([("MODE", "Local")]).is_err());
+    let mode: Mode = desenv::load_from_iter([("MODE", "Local"), ("COUNT", "2")]).unwrap();
+    assert!(matches!(mode, Mode::Local { count: 2 }));
+}
+
+#[test]
+fn check_invalid_defaults() {
+    let errors: Vec<String> = match desenv::check_defaults::<Invalid>().unwrap_err() {
+        Error::Multiple(errors) => errors.iter().map(ToString::to_string).collect(),
+        error => panic!("expected multiple errors, got {error}"),
+    };
+
+    assert_eq!(
+        errors,
+        vec![
+            "Cannot parse env var `PORT` into `Invalid.port`: invalid digit found in string",
+            "Cannot parse env var `IDS` into `Invalid.ids`: invalid digit found in string",
+            "Invalid env var `NAME` for `Invalid.name`: must not be empty",
+            "Cannot parse env var `NESTED_0_COUNT` into `Invalid.nested[0].count`: invalid digit found in string",
+        ]
+    );
+}
@@ -29,3 +29,21 @@ fn field_desenv_macro_attribute_compilation_failures() {
     let test_cases: TestCases = TestCases::new();
     test_cases.compile_fail("test-assets/field/failure/*.rs");
 }
+
+#[test]
+fn value_desenv_macro_compilation_failures() {
+    let test_cases: TestCases = TestCases::new();
+    test_cases.compile_fail("test-assets/value/failure/*.rs");
+}
+
+#[test]
+fn enum_desenv_macro_attribute_compilation_success() {
+    let test_cases: TestCases = TestCases::new();
+    test_cases.pass("test-assets/enum/success/*.rs");
+}
+
+#[test]
+fn enum_desenv_macro_attribute_compilation_failures() {
+    let test_cases: TestCases = TestCases::new();
+    test_cases.compile_fail("test-assets/enum/failure/*.rs");
+}
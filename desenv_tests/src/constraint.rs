@@ -0,0 +1,128 @@
+use desenv::{Desenv, Error};
+
+#[derive(Desenv, Debug)]
+#[desenv(prefix = "TLS_")]
+pub struct Tls {
+    #[desenv(requires = "key")]
+    pub cert: Option<String>,
+    #[desenv(requires = "cert")]
+    pub key: Option<String>,
+    #[desenv(conflicts_with = "cert")]
+    pub insecure: Option<bool>,
+    pub mode: Option<String>,
+    #[desenv(required_if(field = "mode", eq = "mutual"))]
+    pub ca: Option<String>,
+}
+
+#[test]
+fn constraints_are_satisfied() {
+    let tls: Tls = desenv::load_from_iter([
+        ("TLS_CERT", "cert.pem"),
+        ("TLS_KEY", "key.pem"),
+        ("TLS_MODE", "mutual"),
+        ("TLS_CA", "ca.pem"),
+    ])
+    .unwrap();
+
+    assert_eq!(tls.cert.as_deref(), Some("cert.pem"));
+    assert_eq!(tls.key.as_deref(), Some("key.pem"));
+    assert_eq!(tls.insecure, None);
+    assert_eq!(tls.mode.as_deref(), Some("mutual"));
+    assert_eq!(tls.ca.as_deref(), Some("ca.pem"));
+
+    let tls: Tls = desenv::load_from_iter([("TLS_INSECURE", "true"), ("TLS_MODE", "simple")]).unwrap();
+    assert_eq!(tls.insecure, Some(true));
+    assert_eq!(tls.ca, None);
+}
+
+#[test]
+fn requires_fails_naming_both_variables() {
+    let tls: Result<Tls, Error> = desenv::load_from_iter([("TLS_CERT", "cert.pem")]);
+
+    match tls.unwrap_err() {
+        Error::Validation(error) => {
+            assert_eq!(error.var(), "TLS_CERT");
+            assert_eq!(error.path(), "Tls.cert");
+            assert_eq!(error.rule(), "requires `TLS_KEY` to be set");
+        }
+        error => panic!("expected validation error, got {error}"),
+    }
+}
+
+#[test]
+fn conflicts_with_and_required_if_fail_naming_both_variables() {
+    let tls: Result<Tls, Error> = desenv::load_from_iter([
+        ("TLS_CERT", "cert.pem"),
+        ("TLS_KEY", "key.pem"),
+        ("TLS_INSECURE", "true"),
+        ("TLS_MODE", "mutual"),
+    ]);
+
+    let errors: Vec<String> = match tls.unwrap_err() {
+        Error::Multiple(errors) => errors.iter().map(ToString::to_string).collect(),
+        error => panic!("expected multiple errors, got {error}"),
+    };
+    assert_eq!(
+        errors,
+        vec![
+            "Invalid env var `TLS_INSECURE` for `Tls.insecure`: conflicts with `TLS_CERT`",
+            "Invalid env var `TLS_CA` for `Tls.ca`: is required when `TLS_MODE` is `mutual`",
+        ]
+    );
+}
+
+#[derive(Desenv, Debug)]
+pub struct Server {
+    #[desenv(requires = "host")]
+    pub port: u16,
+    pub host: Option<String>,
+}
+
+#[test]
+fn constraint_errors_are_reported_with_field_errors() {
+    let server: Server = desenv::load_from_iter([("PORT", "8080"), ("HOST", "localhost")]).unwrap();
+    assert_eq!(server.port, 8080);
+    assert_eq!(server.host.as_deref(), Some("localhost"));
+
+    let server: Result<Server, Error> = desenv::load_from_iter([("PORT", "not-a-port")]);
+
+    match server.unwrap_err() {
+        Error::Multiple(errors) => assert_eq!(errors.len(), 2),
+        error => panic!("expected multiple errors, got {error}"),
+    }
+}
+
+#[derive(Desenv, Debug)]
+pub struct Listener {
+    #[desenv(default = "plain")]
+    pub mode: String,
+    #[desenv(required_if(field = "mode", eq = "plain"))]
+    pub cert: Option<String>,
+    #[desenv(requires = "port")]
+    pub host: Option<String>,
+    #[desenv(default = "8080")]
+    pub port: u16,
+}
+
+#[test]
+fn constraints_take_defaults_into_account() {
+    let listener: Result<Listener, Error> = desenv::load_from_iter([("HOST", "localhost")]);
+
+    match listener.unwrap_err() {
+        Error::Validation(error) => {
+            assert_eq!(error.var(), "CERT");
+            assert_eq!(error.path(), "Listener.cert");
+            assert_eq!(error.rule(), "is required when `MODE` is `plain`");
+        }
+        error => panic!("expected validation error, got {error}"),
+    }
+
+    let listener: Listener = desenv::load_from_iter([("HOST", "localhost"), ("CERT", "cert.pem")]).unwrap();
+    assert_eq!(listener.mode, "plain");
+    assert_eq!(listener.cert.as_deref(), Some("cert.pem"));
+    assert_eq!(listener.host.as_deref(), Some("localhost"));
+    assert_eq!(listener.port, 8080);
+
+    let listener: Listener = desenv::load_from_iter([("MODE", "tls")]).unwrap();
+    assert_eq!(listener.cert, None);
+}
@@ -1,5 +1,5 @@
 use desenv::Desenv;
-use desenv::Error;
+use desenv::{Error, ParseError};
 
 use crate::test_utils::EnvUtil;
 
@@ -79,7 +79,13 @@ fn deserialize_i32_field_with_default_field_attr_fail_for_wrong_default() {
     assert!(config.is_err());
     assert_eq!(
         config.unwrap_err().to_string(),
-        Error::Custom("Cannot parse env var: ParseIntError { kind: InvalidDigit }".to_string()).to_string()
+        Error::ParseFromStr(ParseError::new(
+            "FIELD",
+            "AttrWithI32AndWrongValue.field",
+            "default_value",
+            "default_value".parse::<i32>().unwrap_err()
+        ))
+        .to_string()
     );
 }
 
@@ -171,3 +177,53 @@ fn deserialize_without_env_var_set_default_values() {
     let config: VecField = desenv::load().unwrap();
     assert_eq!(config.field, vec![true, false]);
 }
+
+#[derive(Desenv, Debug)]
+pub struct AttrWithEnvChain {
+    #[desenv(default(env = "PORT", env = "HTTP_PORT", value = "8080"))]
+    pub field: u16,
+    #[desenv(default(env = "LEVEL", env = "LOG_LEVEL"))]
+    pub optional: Option<String>,
+    #[desenv(default(env = "HOSTS", value = "a,b"))]
+    pub vector: Vec<String>,
+}
+
+#[test]
+fn deserialize_fields_with_env_chain_default_using_first_env_var_set() {
+    let config: AttrWithEnvChain = desenv::load_from_iter([
+        ("PORT", "80"),
+        ("HTTP_PORT", "81"),
+        ("LOG_LEVEL", "debug"),
+        ("HOSTS", "c"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.field, 80);
+    assert_eq!(config.optional.as_deref(), Some("debug"));
+    assert_eq!(config.vector, vec!["c".to_string()]);
+
+    let config: AttrWithEnvChain = desenv::load_from_iter([("HTTP_PORT", "81")]).unwrap();
+    assert_eq!(config.field, 81);
+}
+
+#[test]
+fn deserialize_fields_with_env_chain_default_using_value_default() {
+    let config: AttrWithEnvChain = desenv::load_from_iter::<_, _, String, String>([]).unwrap();
+
+    assert_eq!(config.field, 8080);
+    assert_eq!(config.optional, None);
+    assert_eq!(config.vector, vec!["a".to_string(), "b".to_string()]);
+}
+
+#[test]
+fn deserialize_field_with_env_chain_default_fail_naming_fallback_var() {
+    let config: Result<AttrWithEnvChain, Error> = desenv::load_from_iter([("HTTP_PORT", "port")]);
+
+    match config.unwrap_err() {
+        Error::ParseFromStr(error) => {
+            assert_eq!(error.var(), "HTTP_PORT");
+            assert_eq!(error.value(), Some("port"));
+        }
+        error => panic!("expected parse error, got {error}"),
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use desenv::Desenv;
+
+#[derive(Desenv, Debug)]
+pub struct Config {
+    #[desenv(default_expr = 8080)]
+    pub port: u16,
+    #[desenv(default_expr = "Duration::from_secs(30)")]
+    pub timeout: Duration,
+    #[desenv(default_fn = "default_hosts")]
+    pub hosts: Vec<String>,
+    #[desenv(default_expr = "Some(\"info\".to_string())")]
+    pub level: Option<String>,
+    #[desenv(default_fn = "std::env::temp_dir")]
+    pub dir: PathBuf,
+    #[desenv(map(inline), default_fn = "default_labels")]
+    pub labels: HashMap<String, String>,
+}
+
+fn default_hosts() -> Vec<String> {
+    vec!["localhost".to_string(), "127.0.0.1".to_string()]
+}
+
+fn default_labels() -> HashMap<String, String> {
+    HashMap::from([("env".to_string(), "dev".to_string())])
+}
+
+#[test]
+fn load_typed_defaults_when_missing() {
+    let config: Config = desenv::load_from_iter::<_, _, String, String>([]).unwrap();
+
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.timeout, Duration::from_secs(30));
+    assert_eq!(config.hosts, default_hosts());
+    assert_eq!(config.level.as_deref(), Some("info"));
+    assert_eq!(config.dir, std::env::temp_dir());
+    assert_eq!(config.labels, default_labels());
+}
+
+#[test]
+fn typed_defaults_are_ignored_when_set() {
+    let config: Config = desenv::load_from_iter([
+        ("PORT", "80"),
+        ("TIMEOUT", "5s"),
+        ("HOSTS", "a,b"),
+        ("LEVEL", "debug"),
+        ("DIR", "/tmp/app"),
+        ("LABELS", "env=prod"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.port, 80);
+    assert_eq!(config.timeout, Duration::from_secs(5));
+    assert_eq!(config.hosts, vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(config.level.as_deref(), Some("debug"));
+    assert_eq!(config.dir, PathBuf::from("/tmp/app"));
+    assert_eq!(config.labels, HashMap::from([("env".to_string(), "prod".to_string())]));
+}
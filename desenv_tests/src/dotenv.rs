@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use desenv::dotenv::{self, DotenvError};
+use desenv::{Desenv, Error};
+
+use crate::test_utils::EnvUtil;
+
+#[test]
+fn parse_dotenv_content() {
+    let content: &str = r#"
+# comment
+PLAIN=value
+export EXPORTED = exported value # inline comment
+EMPTY=
+HASH=value#not_a_comment
+SINGLE='literal \n value' # comment
+DOUBLE="escaped\tvalue \"quoted\""
+MULTILINE="first
+second"
+PLAIN=overridden
+"#;
+
+    let vars: HashMap<String, String> = dotenv::parse(content).unwrap();
+    assert_eq!(vars.len(), 7);
+    assert_eq!(vars["PLAIN"], "overridden");
+    assert_eq!(vars["EXPORTED"], "exported value");
+    assert_eq!(vars["EMPTY"], "");
+    assert_eq!(vars["HASH"], "value#not_a_comment");
+    assert_eq!(vars["SINGLE"], "literal \\n value");
+    assert_eq!(vars["DOUBLE"], "escaped\tvalue \"quoted\"");
+    assert_eq!(vars["MULTILINE"], "first\nsecond");
+}
+
+#[test]
+fn fail_to_parse_malformed_dotenv_content() {
+    let error: DotenvError = dotenv::parse("FIELD=value\nNOT A VAR\n").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid dotenv file at line 2: expected `=` after `NOT`"
+    );
+
+    let error: DotenvError = dotenv::parse("FIELD=value\nQUOTED=\"value\n").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid dotenv file at line 2: unterminated quoted value"
+    );
+}
+
+#[derive(Desenv, Debug)]
+pub struct Config {
+    pub field: String,
+    pub number: i32,
+}
+
+#[test]
+fn deserialize_with_dotenv() {
+    let config: Config = desenv::load_with_dotenv("test-assets/dotenv/config.env").unwrap();
+    assert_eq!(config.field, "from_dotenv");
+    assert_eq!(config.number, 42);
+}
+
+#[test]
+fn deserialize_with_dotenv_preferring_env() {
+    let _env_util: EnvUtil = EnvUtil::new("FIELD", "from_env");
+
+    let config: Config = desenv::load_with_dotenv("test-assets/dotenv/config.env").unwrap();
+    assert_eq!(config.field, "from_env");
+    assert_eq!(config.number, 42);
+    assert!(std::env::var("NUMBER").is_err());
+}
+
+#[test]
+fn fail_to_deserialize_with_missing_dotenv() {
+    let config: Result<Config, Error> = desenv::load_with_dotenv("test-assets/dotenv/missing.env");
+    assert!(matches!(config.unwrap_err(), Error::Dotenv(DotenvError::Io(_))));
+}
@@ -0,0 +1,54 @@
+use desenv::{Desenv, Error};
+
+#[derive(Desenv, Debug)]
+pub struct Config {
+    #[desenv(file)]
+    pub password: String,
+    pub user: Option<String>,
+}
+
+#[test]
+fn deserialize_field_from_file() {
+    let config: Config = desenv::load_from_iter([("PASSWORD_FILE", "test-assets/file/secret.txt")]).unwrap();
+    assert_eq!(config.password, "s3cr3t");
+    assert_eq!(config.user, None);
+}
+
+#[test]
+fn deserialize_field_from_var_before_file() {
+    let vars = [("PASSWORD", "value"), ("PASSWORD_FILE", "test-assets/file/secret.txt")];
+
+    let config: Config = desenv::load_from_iter(vars).unwrap();
+    assert_eq!(config.password, "value");
+}
+
+#[test]
+fn deserialize_field_from_file_fail_for_missing_var_and_file_var() {
+    let config: Result<Config, Error> = desenv::load_from_iter(Vec::<(String, String)>::new());
+    assert_eq!(
+        config.unwrap_err().to_string(),
+        Error::MissingVar("PASSWORD".to_string()).to_string()
+    );
+}
+
+#[test]
+fn deserialize_field_from_file_fail_for_unreadable_file() {
+    let config: Result<Config, Error> = desenv::load_from_iter([("PASSWORD_FILE", "test-assets/file/missing.txt")]);
+    assert!(matches!(config.unwrap_err(), Error::UnreadableFile(var, _) if var == "PASSWORD_FILE"));
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(file, prefix = "DB_")]
+pub struct FileConfig {
+    pub password: String,
+    pub port: Option<u16>,
+}
+
+#[test]
+fn deserialize_every_field_from_file_with_struct_attribute() {
+    let vars = [("DB_PASSWORD_FILE", "test-assets/file/secret.txt"), ("DB_PORT", "5432")];
+
+    let config: FileConfig = desenv::load_from_iter(vars).unwrap();
+    assert_eq!(config.password, "s3cr3t");
+    assert_eq!(config.port, Some(5432));
+}
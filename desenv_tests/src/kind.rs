@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use desenv::{Desenv, Error};
+
+type Hosts = Vec<String>;
+type Labels = BTreeMap<String, String>;
+type Timeout = Option<u64>;
+type Delay = Option<Duration>;
+
+#[derive(Desenv, Debug)]
+pub struct FullPaths {
+    pub os: std::ffi::OsString,
+    pub list: std::vec::Vec<u8>,
+    pub optional: core::option::Option<u8>,
+    #[desenv(map(inline))]
+    pub map: std::collections::HashMap<String, u8>,
+}
+
+#[derive(Desenv, Debug)]
+pub struct Aliases {
+    #[desenv(kind = "vec")]
+    pub hosts: Hosts,
+    #[desenv(kind = "map", map(inline))]
+    pub labels: Labels,
+    #[desenv(kind = "option")]
+    pub timeout: Timeout,
+}
+
+#[test]
+fn classify_types_written_with_full_paths() {
+    let config: FullPaths =
+        desenv::load_from_iter([("OS", "os"), ("LIST", "1,2"), ("OPTIONAL", "3"), ("MAP", "a=4")]).unwrap();
+
+    assert_eq!(config.os, "os");
+    assert_eq!(config.list, vec![1, 2]);
+    assert_eq!(config.optional, Some(3));
+    assert_eq!(config.map.get("a"), Some(&4));
+}
+
+#[test]
+fn classify_optional_type_written_with_full_path_when_missing() {
+    let config: FullPaths = desenv::load_from_iter([("OS", "os"), ("LIST", ""), ("MAP", "")]).unwrap();
+    assert_eq!(config.optional, None);
+}
+
+#[test]
+fn classify_type_aliases_using_kind_modifier() {
+    let config: Aliases = desenv::load_from_iter([("HOSTS", "a, b"), ("LABELS", "team=core")]).unwrap();
+
+    assert_eq!(config.hosts, vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(
+        config.labels,
+        BTreeMap::from([("team".to_string(), "core".to_string())])
+    );
+    assert_eq!(config.timeout, None);
+}
+
+#[test]
+fn classify_type_aliases_using_kind_modifier_fail() {
+    let config: Result<Aliases, Error> = desenv::load_from_iter([("LABELS", "team=core"), ("TIMEOUT", "abc")]);
+
+    match config.unwrap_err() {
+        Error::Multiple(errors) => {
+            assert_eq!(errors.len(), 2);
+            assert_eq!(
+                errors[0].to_string(),
+                Error::MissingVar("HOSTS".to_string()).to_string()
+            );
+        }
+        error => panic!("expected multiple errors, got {error}"),
+    }
+}
+
+#[derive(Desenv, Debug)]
+pub struct Parsers {
+    #[desenv(kind = "option")]
+    pub grace: Option<Duration>,
+    #[desenv(kind = "plain")]
+    pub debug: bool,
+    #[desenv(kind = "option", parse_with = "desenv::units::duration")]
+    pub delay: Delay,
+}
+
+#[test]
+fn keep_duration_and_bool_parsers_using_kind_modifier() {
+    let config: Parsers = desenv::load_from_iter([("GRACE", "5s"), ("DEBUG", "yes"), ("DELAY", "1m")]).unwrap();
+
+    assert_eq!(config.grace, Some(Duration::from_secs(5)));
+    assert!(config.debug);
+    assert_eq!(config.delay, Some(Duration::from_secs(60)));
+}
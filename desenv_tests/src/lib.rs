@@ -1,16 +1,54 @@
 #[cfg(test)]
+mod alias;
+#[cfg(test)]
+mod boolean;
+#[cfg(test)]
+mod check_defaults;
+#[cfg(test)]
 mod compile;
 #[cfg(test)]
+mod constraint;
+#[cfg(test)]
 mod default;
 #[cfg(test)]
+mod default_typed;
+#[cfg(test)]
+mod dotenv;
+#[cfg(test)]
+mod file;
+#[cfg(test)]
+mod kind;
+#[cfg(test)]
+mod map;
+#[cfg(test)]
+mod map_field;
+#[cfg(test)]
+mod multiple;
+#[cfg(test)]
 mod nested;
 #[cfg(test)]
+mod parse_with;
+#[cfg(test)]
+mod path;
+#[cfg(test)]
 mod plain;
 #[cfg(test)]
 mod prefix;
 #[cfg(test)]
 mod rename;
 #[cfg(test)]
+mod secret;
+#[cfg(test)]
 mod separator;
 #[cfg(test)]
+mod source;
+#[cfg(test)]
+mod tagged;
+#[cfg(test)]
 mod test_utils;
+#[cfg(test)]
+mod units;
+#[cfg(test)]
+mod validate;
+#[cfg(test)]
+mod value;
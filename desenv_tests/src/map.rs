@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use desenv::{Desenv, Error};
+
+use crate::test_utils::EnvUtil;
+
+#[derive(Desenv, Debug)]
+pub struct Config {
+    pub field: String,
+    #[desenv(default(env = "DEFAULT_ENV"))]
+    pub fallback: i32,
+    #[desenv(nested)]
+    pub nested: Nested,
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(prefix = "NESTED_")]
+pub struct Nested {
+    pub field: Option<bool>,
+}
+
+#[test]
+fn deserialize_from_map() {
+    let map: HashMap<String, String> = HashMap::from([
+        ("FIELD".to_string(), "value".to_string()),
+        ("FALLBACK".to_string(), "1".to_string()),
+        ("NESTED_FIELD".to_string(), "true".to_string()),
+    ]);
+
+    let config: Config = desenv::load_from_map(&map).unwrap();
+    assert_eq!(config.field, "value");
+    assert_eq!(config.fallback, 1);
+    assert_eq!(config.nested.field, Some(true));
+}
+
+#[test]
+fn deserialize_from_iter_using_default_env() {
+    let config: Config = desenv::load_from_iter([("FIELD", "value"), ("DEFAULT_ENV", "2")]).unwrap();
+    assert_eq!(config.field, "value");
+    assert_eq!(config.fallback, 2);
+    assert_eq!(config.nested.field, None);
+}
+
+#[test]
+fn deserialize_from_iter_ignoring_env() {
+    let _env_util: EnvUtil = EnvUtil::new("FIELD", "value");
+
+    let config: Result<Config, Error> = desenv::load_from_iter([("FALLBACK", "1")]);
+    assert!(config.is_err());
+    assert_eq!(
+        config.unwrap_err().to_string(),
+        Error::MissingVar("FIELD".to_string()).to_string()
+    );
+}
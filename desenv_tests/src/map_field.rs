@@ -0,0 +1,126 @@
+use std::collections::{BTreeMap, HashMap};
+
+use desenv::{Desenv, Error, Source};
+
+#[derive(Desenv, Debug)]
+pub struct Config {
+    pub feature_flags: HashMap<String, bool>,
+    #[desenv(rename = "LIMIT", map(key_case = "kebab-case"))]
+    pub limits: BTreeMap<String, u32>,
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(prefix = "APP_")]
+pub struct InlineConfig {
+    #[desenv(map(inline))]
+    pub labels: BTreeMap<String, String>,
+    #[desenv(map(separator = ';', key_separator = ':'), default = "a:1;b:2")]
+    pub weights: HashMap<String, u8>,
+}
+
+#[test]
+fn map_fields_scan_variables_under_prefix() {
+    let config: Config = desenv::load_from_iter([
+        ("FEATURE_FLAGS_DARK_MODE", "true"),
+        ("FEATURE_FLAGS_BETA", "false"),
+        ("FEATURE_FLAGS", "ignored"),
+        ("FEATURE_FLAGS_", "ignored"),
+        ("LIMIT_MAX_CONNECTIONS", "10"),
+        ("OTHER", "ignored"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.feature_flags.len(), 2);
+    assert_eq!(config.feature_flags.get("DARK_MODE"), Some(&true));
+    assert_eq!(config.feature_flags.get("BETA"), Some(&false));
+    assert_eq!(config.limits, BTreeMap::from([("max-connections".to_string(), 10)]));
+}
+
+#[test]
+fn map_fields_are_empty_without_variables() {
+    let config: Config = desenv::load_from_iter::<Config, _, String, String>([]).unwrap();
+    assert!(config.feature_flags.is_empty());
+    assert!(config.limits.is_empty());
+}
+
+#[test]
+fn map_fields_scan_all_the_layers_of_a_source() {
+    let primary: HashMap<String, String> = HashMap::from([("LIMIT_A".to_string(), "1".to_string())]);
+    let fallback: BTreeMap<String, String> = BTreeMap::from([
+        ("LIMIT_A".to_string(), "2".to_string()),
+        ("LIMIT_B".to_string(), "3".to_string()),
+    ]);
+
+    let config: Config = desenv::load_from(&primary.or(fallback)).unwrap();
+    assert_eq!(
+        config.limits,
+        BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 3)])
+    );
+}
+
+#[test]
+fn map_fields_report_the_scanned_variable_on_parse_failure() {
+    let error: Error = desenv::load_from_iter::<Config, _, _, _>([("FEATURE_FLAGS_BETA", "maybe")]).unwrap_err();
+
+    match error {
+        Error::ParseFromStr(error) => {
+            assert_eq!(error.var(), "FEATURE_FLAGS_BETA");
+            assert_eq!(error.path(), "Config.feature_flags");
+            assert_eq!(error.value(), Some("maybe"));
+        }
+        error => panic!("unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn inline_map_fields_parse_a_single_variable() {
+    let config: InlineConfig =
+        desenv::load_from_iter([("APP_LABELS", "team=core, tier = gold,"), ("APP_WEIGHTS", "x:5")]).unwrap();
+
+    assert_eq!(
+        config.labels,
+        BTreeMap::from([
+            ("team".to_string(), "core".to_string()),
+            ("tier".to_string(), "gold".to_string())
+        ])
+    );
+    assert_eq!(config.weights, HashMap::from([("x".to_string(), 5)]));
+}
+
+#[test]
+fn inline_map_fields_use_default_value() {
+    let config: InlineConfig = desenv::load_from_iter([("APP_LABELS", "")]).unwrap();
+
+    assert!(config.labels.is_empty());
+    assert_eq!(
+        config.weights,
+        HashMap::from([("a".to_string(), 1), ("b".to_string(), 2)])
+    );
+}
+
+#[test]
+fn inline_map_fields_fail_if_missing() {
+    let error: Error = desenv::load_from_iter::<InlineConfig, _, String, String>([]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        Error::MissingVar("APP_LABELS".to_string()).to_string()
+    );
+}
+
+#[test]
+fn inline_map_fields_fail_without_key_separator() {
+    let error: Error = desenv::load_from_iter::<InlineConfig, _, _, _>([("APP_LABELS", "team")]).unwrap_err();
+
+    match error {
+        Error::ParseFromStr(error) => {
+            assert_eq!(error.var(), "APP_LABELS");
+            assert_eq!(error.path(), "InlineConfig.labels");
+            assert_eq!(error.value(), Some("team"));
+            assert_eq!(
+                error.to_string(),
+                "Cannot parse env var `APP_LABELS` into `InlineConfig.labels`: missing key/value separator `=`"
+            );
+        }
+        error => panic!("unexpected error: {error}"),
+    }
+}
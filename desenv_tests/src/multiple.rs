@@ -0,0 +1,60 @@
+use desenv::{Desenv, Error};
+
+#[derive(Desenv, Debug)]
+pub struct Config {
+    pub field: String,
+    pub number: i32,
+    pub flags: Vec<bool>,
+    #[desenv(nested)]
+    pub nested: Nested,
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(prefix = "NESTED_")]
+pub struct Nested {
+    pub field: String,
+    pub other: String,
+}
+
+#[test]
+fn collect_errors_of_every_field_including_nested_ones() {
+    let config: Result<Config, Error> = desenv::load_from_iter([("NUMBER", "abc"), ("FLAGS", "yes,maybe")]);
+
+    match config.unwrap_err() {
+        Error::Multiple(errors) => assert_eq!(errors.len(), 5),
+        error => panic!("expected multiple errors, got {error}"),
+    }
+}
+
+#[test]
+fn display_every_collected_error() {
+    let config: Result<Nested, Error> = desenv::load_from_iter(Vec::<(String, String)>::new());
+    assert_eq!(
+        config.unwrap_err().to_string(),
+        "Found 2 errors:\n- Missing env var `NESTED_FIELD`\n- Missing env var `NESTED_OTHER`"
+    );
+}
+
+#[test]
+fn return_single_error_as_is() {
+    let config: Result<Nested, Error> = desenv::load_from_iter([("NESTED_FIELD", "value")]);
+    assert!(matches!(config.unwrap_err(), Error::MissingVar(var) if var == "NESTED_OTHER"));
+}
+
+#[test]
+fn no_error_when_every_field_is_loaded() {
+    let vars = [
+        ("FIELD", "value"),
+        ("NUMBER", "1"),
+        ("FLAGS", "true"),
+        ("NESTED_FIELD", "nested"),
+        ("NESTED_OTHER", "other"),
+    ];
+
+    let config: Config = desenv::load_from_iter(vars).unwrap();
+    assert_eq!(config.field, "value");
+    assert_eq!(config.number, 1);
+    assert_eq!(config.flags, vec![true]);
+    assert_eq!(config.nested.field, "nested");
+    assert_eq!(config.nested.other, "other");
+}
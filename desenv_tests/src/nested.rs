@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use desenv::{Desenv, Error};
 
 use crate::test_utils::EnvUtil;
@@ -31,3 +33,214 @@ fn deserialize_nested_struct_field_fail() {
         Error::MissingVar("FIELD".to_string()).to_string()
     );
 }
+
+#[test]
+fn deserialize_nested_struct_field_fail_reporting_field_path() {
+    let config: Result<CustomNestedField, Error> = desenv::load_from_iter([("FIELD", "abc")]);
+
+    match config.unwrap_err() {
+        Error::ParseFromStr(error) => assert_eq!(error.path(), "CustomNestedField.field.field"),
+        error => panic!("expected parse error, got {error}"),
+    }
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(prefix = "APP_")]
+pub struct Upstreams {
+    #[desenv(nested)]
+    pub upstreams: Vec<Upstream>,
+    #[desenv(nested(prefix = "BACKUP_"))]
+    pub backups: Vec<Upstream>,
+}
+
+#[derive(Desenv, Debug)]
+pub struct Upstream {
+    pub host: String,
+    #[desenv(default(env = "DEFAULT_PORT"))]
+    pub port: u16,
+}
+
+#[test]
+fn deserialize_nested_vector_field_probing_indices() {
+    let config: Upstreams = desenv::load_from_iter([
+        ("APP_UPSTREAMS_0_HOST", "a.local"),
+        ("APP_UPSTREAMS_0_PORT", "80"),
+        ("APP_UPSTREAMS_1_HOST", "b.local"),
+        ("APP_UPSTREAMS_3_HOST", "after.gap"),
+        ("APP_BACKUP_0_HOST", "backup.local"),
+        ("DEFAULT_PORT", "8080"),
+    ])
+    .unwrap();
+
+    let upstreams: Vec<(&str, u16)> = config.upstreams.iter().map(|u| (u.host.as_str(), u.port)).collect();
+    assert_eq!(upstreams, vec![("a.local", 80), ("b.local", 8080)]);
+    assert_eq!(config.backups.len(), 1);
+    assert_eq!(config.backups[0].host, "backup.local");
+}
+
+#[test]
+fn deserialize_nested_vector_field_without_variables() {
+    let config: Upstreams = desenv::load_from_iter([("DEFAULT_PORT", "8080")]).unwrap();
+    assert!(config.upstreams.is_empty());
+    assert!(config.backups.is_empty());
+}
+
+#[test]
+fn deserialize_nested_vector_field_fail_reporting_index() {
+    let config: Result<Upstreams, Error> = desenv::load_from_iter([
+        ("APP_UPSTREAMS_0_HOST", "a.local"),
+        ("APP_UPSTREAMS_0_PORT", "80"),
+        ("APP_UPSTREAMS_1_HOST", "b.local"),
+        ("APP_UPSTREAMS_2_HOST", "c.local"),
+        ("APP_UPSTREAMS_2_PORT", "abc"),
+    ]);
+
+    match config.unwrap_err() {
+        Error::Multiple(errors) => {
+            assert_eq!(errors.len(), 2);
+            assert_eq!(
+                errors[0].to_string(),
+                Error::MissingVar("APP_UPSTREAMS_1_PORT".to_string()).to_string()
+            );
+            match &errors[1] {
+                Error::ParseFromStr(error) => {
+                    assert_eq!(error.var(), "APP_UPSTREAMS_2_PORT");
+                    assert_eq!(error.path(), "Upstreams.upstreams[2].port");
+                }
+                error => panic!("expected parse error, got {error}"),
+            }
+        }
+        error => panic!("expected multiple errors, got {error}"),
+    }
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(rename_all = "kebab-case")]
+pub struct KebabUpstreams {
+    #[desenv(nested)]
+    pub up_streams: Vec<Upstream>,
+}
+
+#[test]
+fn deserialize_nested_vector_field_casing_only_the_field_name() {
+    let config: KebabUpstreams =
+        desenv::load_from_iter([("up-streams_0_HOST", "a.local"), ("up-streams_0_PORT", "80")]).unwrap();
+
+    assert_eq!(config.up_streams.len(), 1);
+    assert_eq!(config.up_streams[0].host, "a.local");
+    assert_eq!(config.up_streams[0].port, 80);
+}
+
+#[derive(Desenv, Debug)]
+pub struct OptionalNested {
+    #[desenv(nested(prefix = "DB_"))]
+    pub database: Option<Database>,
+}
+
+#[derive(Desenv, Debug)]
+pub struct Database {
+    pub host: String,
+    pub port: u16,
+    pub user: Option<String>,
+    #[desenv(default = "5")]
+    pub pool: u8,
+}
+
+#[test]
+fn deserialize_optional_nested_struct_field_without_variables() {
+    let config: OptionalNested = desenv::load_from_iter([("HOST", "unrelated")]).unwrap();
+    assert!(config.database.is_none());
+}
+
+#[test]
+fn deserialize_optional_nested_struct_field_with_required_variables() {
+    let config: OptionalNested = desenv::load_from_iter([("DB_HOST", "db.local"), ("DB_PORT", "5432")]).unwrap();
+
+    let database: Database = config.database.unwrap();
+    assert_eq!(database.host, "db.local");
+    assert_eq!(database.port, 5432);
+    assert_eq!(database.user, None);
+    assert_eq!(database.pool, 5);
+}
+
+#[test]
+fn deserialize_optional_nested_struct_field_fail_if_partially_set() {
+    let config: Result<OptionalNested, Error> = desenv::load_from_iter([("DB_USER", "admin")]);
+
+    match config.unwrap_err() {
+        Error::Multiple(errors) => {
+            let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            assert_eq!(
+                errors,
+                vec![
+                    Error::MissingVar("DB_HOST".to_string()).to_string(),
+                    Error::MissingVar("DB_PORT".to_string()).to_string()
+                ]
+            );
+        }
+        error => panic!("expected multiple errors, got {error}"),
+    }
+}
+
+#[derive(Desenv, Debug)]
+pub struct UnprefixedOptionalNested {
+    #[desenv(nested)]
+    pub database: Option<PrefixedDatabase>,
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(prefix = "DB_")]
+pub struct PrefixedDatabase {
+    pub host: String,
+    #[desenv(default(env = "PORT"))]
+    pub port: u16,
+}
+
+#[test]
+fn deserialize_optional_nested_struct_field_ignoring_default_env_variables() {
+    let config: UnprefixedOptionalNested = desenv::load_from_iter([("PORT", "80")]).unwrap();
+    assert!(config.database.is_none());
+
+    let config: UnprefixedOptionalNested = desenv::load_from_iter([("PORT", "80"), ("DB_HOST", "db.local")]).unwrap();
+    let database: PrefixedDatabase = config.database.unwrap();
+    assert_eq!(database.host, "db.local");
+    assert_eq!(database.port, 80);
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(prefix = "APP_")]
+pub struct ScannedOptionalNested {
+    pub name: String,
+    #[desenv(nested)]
+    pub server: Option<Server>,
+}
+
+#[derive(Desenv, Debug)]
+pub struct Server {
+    pub host: String,
+    pub flags: HashMap<String, String>,
+}
+
+#[test]
+fn deserialize_optional_nested_struct_field_with_map_field() {
+    let config: ScannedOptionalNested = desenv::load_from_iter([("APP_NAME", "app")]).unwrap();
+    assert_eq!(config.name, "app");
+    assert!(config.server.is_none());
+
+    let config: Result<ScannedOptionalNested, Error> =
+        desenv::load_from_iter([("APP_NAME", "app"), ("APP_FLAGS_DEBUG", "on")]);
+    match config.unwrap_err() {
+        Error::MissingVar(var) => assert_eq!(var, "APP_HOST"),
+        error => panic!("expected missing var error, got {error}"),
+    }
+
+    let config: ScannedOptionalNested = desenv::load_from_iter([
+        ("APP_NAME", "app"),
+        ("APP_HOST", "localhost"),
+        ("APP_FLAGS_DEBUG", "on"),
+    ])
+    .unwrap();
+    let server: Server = config.server.unwrap();
+    assert_eq!(server.host, "localhost");
+    assert_eq!(server.flags, HashMap::from([("DEBUG".to_string(), "on".to_string())]));
+}
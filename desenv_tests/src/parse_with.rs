@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter};
+
+use desenv::{Desenv, Error};
+
+// A type without `FromStr`.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Rgb(u8, u8, u8);
+
+#[derive(Debug)]
+pub struct RgbError(String);
+
+impl Display for RgbError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid color `{}`", self.0)
+    }
+}
+
+impl StdError for RgbError {}
+
+fn parse_rgb(value: &str) -> Result<Rgb, RgbError> {
+    let hex = |index: usize| -> Result<u8, RgbError> {
+        value
+            .get(index..index + 2)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .ok_or_else(|| RgbError(value.to_string()))
+    };
+
+    match value.strip_prefix('#') {
+        Some(rest) if rest.len() == 6 => Ok(Rgb(hex(1)?, hex(3)?, hex(5)?)),
+        _ => Err(RgbError(value.to_string())),
+    }
+}
+
+mod parsers {
+    pub fn double(value: &str) -> Result<u32, std::num::ParseIntError> {
+        value.parse::<u32>().map(|value| value * 2)
+    }
+}
+
+#[derive(Desenv, Debug)]
+pub struct Theme {
+    #[desenv(parse_with = "parse_rgb")]
+    pub primary: Rgb,
+    #[desenv(parse_with = "parse_rgb")]
+    pub secondary: Option<Rgb>,
+    #[desenv(parse_with = "parse_rgb", default = "#000000")]
+    pub background: Rgb,
+    #[desenv(parse_with = "parse_rgb", separator = ';')]
+    pub palette: Vec<Rgb>,
+    #[desenv(parse_with = "parsers::double", map(inline))]
+    pub sizes: BTreeMap<String, u32>,
+}
+
+#[test]
+fn deserialize_fields_with_custom_parser() {
+    let config: Theme = desenv::load_from_iter([
+        ("PRIMARY", "#ff0000"),
+        ("PALETTE", "#010203;#0a0b0c"),
+        ("SIZES", "small=1,large=4"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.primary, Rgb(255, 0, 0));
+    assert_eq!(config.secondary, None);
+    assert_eq!(config.background, Rgb(0, 0, 0));
+    assert_eq!(config.palette, vec![Rgb(1, 2, 3), Rgb(10, 11, 12)]);
+    assert_eq!(
+        config.sizes,
+        BTreeMap::from([("large".to_string(), 8), ("small".to_string(), 2)])
+    );
+}
+
+#[test]
+fn deserialize_fields_with_custom_parser_fail_keeping_error_as_source() {
+    let config: Result<Theme, Error> = desenv::load_from_iter([("PRIMARY", "red"), ("PALETTE", ""), ("SIZES", "")]);
+
+    let error: Error = config.unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Cannot parse env var `PRIMARY` into `Theme.primary`: invalid color `red`"
+    );
+    let source: &(dyn StdError + 'static) = error.source().unwrap();
+    assert!(source.downcast_ref::<RgbError>().is_some());
+}
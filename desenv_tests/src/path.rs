@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::os::unix::ffi::OsStringExt;
+use std::path::PathBuf;
+
+use desenv::{Desenv, Error, Source};
+
+// A source able to hold non-unicode values.
+struct OsSource(HashMap<&'static str, OsString>);
+
+impl Source for OsSource {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        self.0.get(key).cloned()
+    }
+}
+
+#[derive(Desenv, Debug)]
+pub struct Paths {
+    pub data: PathBuf,
+    pub cache: Option<PathBuf>,
+    pub plugins: Vec<PathBuf>,
+    #[desenv(separator = ',')]
+    pub extra: Vec<std::path::PathBuf>,
+    #[desenv(default = "/tmp")]
+    pub tmp: PathBuf,
+}
+
+fn non_unicode(prefix: &str) -> OsString {
+    let mut bytes: Vec<u8> = prefix.as_bytes().to_vec();
+    bytes.push(255);
+    OsString::from_vec(bytes)
+}
+
+#[test]
+fn deserialize_non_unicode_paths() {
+    let mut plugins: Vec<u8> = non_unicode("/a").into_vec();
+    plugins.extend_from_slice(b"::/b");
+    let mut extra: Vec<u8> = non_unicode("/c").into_vec();
+    extra.extend_from_slice(b",/d:e,");
+
+    let source: OsSource = OsSource(HashMap::from([
+        ("DATA", non_unicode("/data")),
+        ("CACHE", non_unicode("/cache")),
+        ("PLUGINS", OsString::from_vec(plugins)),
+        ("EXTRA", OsString::from_vec(extra)),
+    ]));
+
+    let config: Paths = desenv::load_from(&source).unwrap();
+    assert_eq!(config.data, PathBuf::from(non_unicode("/data")));
+    assert_eq!(config.cache, Some(PathBuf::from(non_unicode("/cache"))));
+    assert_eq!(
+        config.plugins,
+        vec![PathBuf::from(non_unicode("/a")), PathBuf::from("/b")]
+    );
+    assert_eq!(
+        config.extra,
+        vec![PathBuf::from(non_unicode("/c")), PathBuf::from("/d:e")]
+    );
+    assert_eq!(config.tmp, PathBuf::from("/tmp"));
+}
+
+#[test]
+fn deserialize_missing_optional_path() {
+    let config: Paths = desenv::load_from_iter([("DATA", "/data"), ("PLUGINS", ""), ("EXTRA", "")]).unwrap();
+    assert_eq!(config.cache, None);
+    assert!(config.plugins.is_empty());
+    assert!(config.extra.is_empty());
+}
+
+#[test]
+fn deserialize_missing_required_paths_fail() {
+    let config: Result<Paths, Error> = desenv::load_from_iter([("CACHE", "/cache")]);
+
+    match config.unwrap_err() {
+        Error::Multiple(errors) => {
+            let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            assert_eq!(
+                errors,
+                vec![
+                    Error::MissingVar("DATA".to_string()).to_string(),
+                    Error::MissingVar("PLUGINS".to_string()).to_string(),
+                    Error::MissingVar("EXTRA".to_string()).to_string()
+                ]
+            );
+        }
+        error => panic!("expected multiple errors, got {error}"),
+    }
+}
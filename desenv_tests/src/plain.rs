@@ -2,7 +2,7 @@ use std::ffi::OsString;
 use std::os::unix::ffi::OsStringExt;
 
 use desenv::Desenv;
-use desenv::Error;
+use desenv::{Error, ParseError};
 
 use crate::test_utils::EnvUtil;
 
@@ -139,7 +139,13 @@ fn fail_to_deserialize_wrong_list_of_values() {
     assert!(config.is_err());
     assert_eq!(
         config.unwrap_err().to_string(),
-        Error::ParseFromStr("ParseBoolError".to_string()).to_string()
+        Error::ParseFromStr(ParseError::new(
+            "FIELD",
+            "VecField.field",
+            "true1",
+            desenv::parse_bool("true1").unwrap_err()
+        ))
+        .to_string()
     );
 }
 
@@ -192,10 +198,36 @@ fn deserialize_custom_enum_field_with_invalid_value() {
     assert!(config.is_err());
     assert_eq!(
         config.unwrap_err().to_string(),
-        Error::ParseFromStr("invalid value val_12".to_string()).to_string()
+        Error::ParseFromStr(ParseError::new(
+            "FIELD",
+            "CustomEnumField.field",
+            "val_12",
+            Error::Custom("invalid value val_12".to_string())
+        ))
+        .to_string()
     );
 }
 
+#[test]
+fn deserialize_custom_enum_field_with_invalid_value_keeping_error_details() {
+    let _env_util: EnvUtil = EnvUtil::new("FIELD", "val_12");
+
+    let error: Error = desenv::load::<CustomEnumField>().unwrap_err();
+    assert_eq!(
+        std::error::Error::source(&error).map(ToString::to_string),
+        Some("invalid value val_12".to_string())
+    );
+    match error {
+        Error::ParseFromStr(error) => {
+            assert_eq!(error.var(), "FIELD");
+            assert_eq!(error.path(), "CustomEnumField.field");
+            assert_eq!(error.value(), Some("val_12"));
+            assert_eq!(error.redact().value(), None);
+        }
+        error => panic!("expected parse error, got {error}"),
+    }
+}
+
 #[test]
 fn deserialize_custom_enum_field_with_empty_value() {
     let config: Result<CustomEnumField, Error> = desenv::load();
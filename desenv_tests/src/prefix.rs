@@ -65,3 +65,36 @@ fn deserialize_prefixed_config_with_nested_prefixed_struct_field_fail() {
         Error::MissingVar("PARENT_PREFIX_FIELD".to_string()).to_string()
     );
 }
+
+#[derive(Desenv, Debug)]
+#[desenv(prefix = "DB_")]
+pub struct DbConfig {
+    pub host: String,
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(prefix = "APP_")]
+pub struct ConfigWithNestedFieldPrefixes {
+    #[desenv(nested)]
+    pub primary: DbConfig,
+    #[desenv(nested(prefix = "REPLICA_"))]
+    pub replica: DbConfig,
+}
+
+#[test]
+fn deserialize_same_nested_struct_with_different_field_prefixes() {
+    let vars = [("APP_DB_HOST", "primary"), ("APP_REPLICA_DB_HOST", "replica")];
+
+    let config: ConfigWithNestedFieldPrefixes = desenv::load_from_iter(vars).unwrap();
+    assert_eq!(config.primary.host, "primary");
+    assert_eq!(config.replica.host, "replica");
+}
+
+#[test]
+fn deserialize_same_nested_struct_with_different_field_prefixes_fail() {
+    let config: Result<ConfigWithNestedFieldPrefixes, Error> = desenv::load_from_iter([("APP_DB_HOST", "primary")]);
+    assert_eq!(
+        config.unwrap_err().to_string(),
+        Error::MissingVar("APP_REPLICA_DB_HOST".to_string()).to_string()
+    );
+}
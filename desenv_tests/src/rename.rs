@@ -30,3 +30,39 @@ fn deserialize_simple_string_field_with_rename_field_attr_fail() {
         Error::MissingVar("rename_field".to_string()).to_string()
     );
 }
+
+#[derive(Desenv, Debug)]
+#[desenv(rename_all = "kebab-case", prefix = "app.")]
+pub struct KebabCase {
+    pub field_name: String,
+    #[desenv(rename = "OTHER")]
+    pub other_field: String,
+}
+
+#[test]
+fn deserialize_fields_with_rename_all_struct_attr() {
+    let config: KebabCase = desenv::load_from_iter([("app.field-name", "value"), ("app.OTHER", "other")]).unwrap();
+    assert_eq!(config.field_name, "value");
+    assert_eq!(config.other_field, "other");
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(rename_all = "camelCase")]
+pub struct CamelCase {
+    pub http_server_port: u16,
+}
+
+#[test]
+fn deserialize_fields_with_rename_all_struct_attr_fail() {
+    let config: Result<CamelCase, Error> = desenv::load_from_iter([("HTTP_SERVER_PORT", "80")]);
+    assert_eq!(
+        config.unwrap_err().to_string(),
+        Error::MissingVar("httpServerPort".to_string()).to_string()
+    );
+}
+
+#[test]
+fn deserialize_fields_with_rename_all_camel_case() {
+    let config: CamelCase = desenv::load_from_iter([("httpServerPort", "80")]).unwrap();
+    assert_eq!(config.http_server_port, 80);
+}
@@ -0,0 +1,111 @@
+use std::error::Error as StdError;
+
+use desenv::{Desenv, Error, Secret};
+
+#[derive(Desenv, Debug)]
+pub struct Database {
+    pub password: Secret<String>,
+    pub pin: Option<Secret<u32>>,
+    #[desenv(secret)]
+    pub token: u64,
+    pub port: u16,
+}
+
+#[derive(Desenv, Debug)]
+pub struct Worker {
+    #[desenv(validate_with = "even")]
+    pub seed: Secret<u32>,
+    #[desenv(secret, validate(range(max = 10)))]
+    pub shard: u32,
+}
+
+// `validate_with` functions take the value by reference.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn even(value: &Secret<u32>) -> Result<(), String> {
+    if value.expose() % 2 == 0 {
+        Ok(())
+    } else {
+        Err(format!("{} is not even", value.expose()))
+    }
+}
+
+#[test]
+fn secrets_are_redacted_in_debug_and_display() {
+    let config: Database = desenv::load_from_iter([
+        ("PASSWORD", "hunter2"),
+        ("PIN", "1234"),
+        ("TOKEN", "42"),
+        ("PORT", "5432"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.password.expose(), "hunter2");
+    assert_eq!(config.pin.map(Secret::into_inner), Some(1234));
+    assert_eq!(config.token, 42);
+    assert_eq!(config.port, 5432);
+    assert_eq!(config.password.to_string(), "[REDACTED]");
+
+    let debug: String = format!("{:?}", config.password);
+    assert_eq!(debug, "[REDACTED]");
+}
+
+#[test]
+fn secret_field_errors_never_contain_the_raw_value() {
+    let config: Result<Database, Error> = desenv::load_from_iter([
+        ("PASSWORD", "hunter2"),
+        ("PIN", "12a4"),
+        ("TOKEN", "s3cr3t"),
+        ("PORT", "abc"),
+    ]);
+
+    let error: Error = config.unwrap_err();
+    let display: String = error.to_string();
+    let debug: String = format!("{error:?}");
+    assert!(!display.contains("12a4") && !debug.contains("12a4"));
+    assert!(!display.contains("s3cr3t") && !debug.contains("s3cr3t"));
+
+    match error {
+        Error::Multiple(errors) => {
+            assert_eq!(errors.len(), 3);
+            assert_eq!(
+                errors[0].to_string(),
+                "Cannot parse env var `PIN` into `Database.pin`: [REDACTED]"
+            );
+            match &errors[1] {
+                Error::ParseFromStr(error) => {
+                    assert_eq!(error.var(), "TOKEN");
+                    assert_eq!(error.value(), None);
+                    assert_eq!(error.source().unwrap().to_string(), "[REDACTED]");
+                }
+                error => panic!("expected parse error, got {error}"),
+            }
+            match &errors[2] {
+                Error::ParseFromStr(error) => assert_eq!(error.value(), Some("abc")),
+                error => panic!("expected parse error, got {error}"),
+            }
+        }
+        error => panic!("expected multiple errors, got {error}"),
+    }
+}
+
+#[test]
+fn secret_field_custom_validation_errors_never_contain_the_raw_value() {
+    let config: Worker = desenv::load_from_iter([("SEED", "1234"), ("SHARD", "3")]).unwrap();
+    assert_eq!((*config.seed.expose(), config.shard), (1234, 3));
+
+    let config: Result<Worker, Error> = desenv::load_from_iter([("SEED", "12345"), ("SHARD", "11")]);
+
+    match config.unwrap_err() {
+        Error::Multiple(errors) => {
+            let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            assert_eq!(
+                errors,
+                vec![
+                    "Invalid env var `SEED` for `Worker.seed`: custom validation failed",
+                    "Invalid env var `SHARD` for `Worker.shard`: must be at most 10",
+                ]
+            );
+        }
+        error => panic!("expected multiple errors, got {error}"),
+    }
+}
@@ -1,4 +1,4 @@
-use desenv::{Desenv, Error};
+use desenv::{Desenv, Error, ParseError};
 
 use crate::test_utils::EnvUtil;
 
@@ -24,6 +24,12 @@ fn deserialize_vec_with_custom_separator_fail() {
     assert!(config.is_err());
     assert_eq!(
         config.unwrap_err().to_string(),
-        Error::ParseFromStr("ParseBoolError".to_string()).to_string()
+        Error::ParseFromStr(ParseError::new(
+            "FIELD",
+            "VecField.field",
+            "true, false",
+            desenv::parse_bool("true, false").unwrap_err()
+        ))
+        .to_string()
     );
 }
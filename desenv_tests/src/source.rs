@@ -0,0 +1,59 @@
+use std::ffi::OsString;
+
+use desenv::{Desenv, Error, Source};
+
+use crate::test_utils::EnvUtil;
+
+struct FixedSource(&'static [(&'static str, &'static str)]);
+
+impl Source for FixedSource {
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        self.0.iter().find(|(k, _)| *k == key).map(|(_, v)| OsString::from(v))
+    }
+}
+
+#[derive(Desenv, Debug)]
+pub struct Simple {
+    pub field: String,
+}
+
+#[test]
+fn deserialize_from_custom_source() {
+    let config: Simple = desenv::load_from(&FixedSource(&[("FIELD", "value")])).unwrap();
+    assert_eq!(config.field, "value");
+}
+
+#[test]
+fn deserialize_from_custom_source_ignoring_env() {
+    let _env_util: EnvUtil = EnvUtil::new("FIELD", "value");
+
+    let config: Result<Simple, Error> = desenv::load_from(&FixedSource(&[]));
+    assert!(config.is_err());
+    assert_eq!(
+        config.unwrap_err().to_string(),
+        Error::MissingVar("FIELD".to_string()).to_string()
+    );
+}
+
+#[derive(Desenv, Debug)]
+pub struct Config {
+    #[desenv(default(env = "DEFAULT_ENV"))]
+    pub field: i32,
+    #[desenv(nested)]
+    pub nested: Nested,
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(prefix = "NESTED_")]
+pub struct Nested {
+    pub field: Vec<String>,
+}
+
+#[test]
+fn deserialize_default_env_and_nested_from_custom_source() {
+    let source: FixedSource = FixedSource(&[("DEFAULT_ENV", "10"), ("NESTED_FIELD", "a,b")]);
+
+    let config: Config = desenv::load_from(&source).unwrap();
+    assert_eq!(config.field, 10);
+    assert_eq!(config.nested.field, vec!["a", "b"]);
+}
@@ -0,0 +1,85 @@
+use desenv::{Desenv, Error};
+
+#[derive(Desenv, Debug, Eq, PartialEq)]
+#[desenv(tag = "KIND", prefix = "STORAGE_", rename_all = "lowercase")]
+pub enum Storage {
+    S3 {
+        bucket: String,
+        region: String,
+    },
+    #[desenv(prefix = "LOCAL_", alias = "fs")]
+    Local {
+        path: String,
+        #[desenv(default = "false")]
+        create: bool,
+    },
+    Memory,
+}
+
+#[derive(Desenv, Debug)]
+#[desenv(prefix = "APP_")]
+pub struct Config {
+    #[desenv(nested)]
+    pub storage: Storage,
+}
+
+#[test]
+fn deserialize_tagged_enum_variant() {
+    let vars = [
+        ("STORAGE_KIND", "s3"),
+        ("STORAGE_BUCKET", "bucket"),
+        ("STORAGE_REGION", "eu"),
+    ];
+
+    let storage: Storage = desenv::load_from_iter(vars).unwrap();
+    assert_eq!(
+        storage,
+        Storage::S3 { bucket: "bucket".to_string(), region: "eu".to_string() }
+    );
+}
+
+#[test]
+fn deserialize_tagged_enum_variant_with_prefix_and_alias() {
+    let storage: Storage = desenv::load_from_iter([("STORAGE_KIND", "fs"), ("STORAGE_LOCAL_PATH", "/tmp")]).unwrap();
+    assert_eq!(storage, Storage::Local { path: "/tmp".to_string(), create: false });
+}
+
+#[test]
+fn deserialize_tagged_enum_unit_variant() {
+    let storage: Storage = desenv::load_from_iter([("STORAGE_KIND", "memory"), ("STORAGE_BUCKET", "bucket")]).unwrap();
+    assert_eq!(storage, Storage::Memory);
+}
+
+#[test]
+fn deserialize_nested_tagged_enum() {
+    let config: Config = desenv::load_from_iter([("APP_STORAGE_KIND", "memory")]).unwrap();
+    assert_eq!(config.storage, Storage::Memory);
+}
+
+#[test]
+fn deserialize_tagged_enum_fail_only_for_selected_variant_fields() {
+    let storage: Result<Storage, Error> = desenv::load_from_iter([("STORAGE_KIND", "local")]);
+    assert_eq!(
+        storage.unwrap_err().to_string(),
+        Error::MissingVar("STORAGE_LOCAL_PATH".to_string()).to_string()
+    );
+}
+
+#[test]
+fn deserialize_tagged_enum_fail_for_missing_tag() {
+    let storage: Result<Storage, Error> = desenv::load_from_iter([("STORAGE_BUCKET", "bucket")]);
+    assert_eq!(
+        storage.unwrap_err().to_string(),
+        Error::MissingVar("STORAGE_KIND".to_string()).to_string()
+    );
+}
+
+#[test]
+fn deserialize_tagged_enum_fail_for_unknown_tag() {
+    let config: Result<Config, Error> = desenv::load_from_iter([("APP_STORAGE_KIND", "gcs")]);
+    assert_eq!(
+        config.unwrap_err().to_string(),
+        "Cannot parse env var `APP_STORAGE_KIND` into `Config.storage`: unknown variant `gcs`, expected one of `s3`, \
+         `local`, `fs`, `memory`"
+    );
+}
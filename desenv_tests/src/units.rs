@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use desenv::units::{self, ByteSize};
+use desenv::{Desenv, Error};
+
+#[derive(Desenv, Debug)]
+pub struct Limits {
+    pub timeout: Duration,
+    #[desenv(default = "1h30m")]
+    pub idle: std::time::Duration,
+    pub grace: Option<Duration>,
+    pub retries: Vec<Duration>,
+    #[desenv(map(inline))]
+    pub per_route: HashMap<String, Duration>,
+    pub buffer: ByteSize,
+    #[desenv(default = "10MB")]
+    pub max_body: ByteSize,
+}
+
+#[test]
+fn parse_durations() {
+    assert_eq!(units::duration("0").unwrap(), Duration::ZERO);
+    assert_eq!(units::duration("250ms").unwrap(), Duration::from_millis(250));
+    assert_eq!(units::duration("2s").unwrap(), Duration::from_secs(2));
+    assert_eq!(units::duration("1h30m").unwrap(), Duration::from_secs(5400));
+    assert_eq!(units::duration("1d 2h").unwrap(), Duration::from_secs(93600));
+    assert_eq!(units::duration("1.5s").unwrap(), Duration::from_millis(1500));
+    assert_eq!(units::duration("10us").unwrap(), Duration::from_micros(10));
+    assert_eq!(units::duration("10µs 5ns").unwrap(), Duration::from_nanos(10005));
+}
+
+#[test]
+fn parse_invalid_durations() {
+    assert_eq!(units::duration("").unwrap_err().to_string(), "empty duration");
+    assert_eq!(
+        units::duration("10").unwrap_err().to_string(),
+        "missing unit in duration `10`"
+    );
+    assert_eq!(
+        units::duration("10y").unwrap_err().to_string(),
+        "unknown unit `y` in duration `10y`"
+    );
+    assert_eq!(units::duration("s").unwrap_err().to_string(), "invalid duration `s`");
+    assert_eq!(
+        units::duration("1.2.3s").unwrap_err().to_string(),
+        "invalid duration `1.2.3s`"
+    );
+}
+
+#[test]
+fn parse_byte_sizes() {
+    assert_eq!(units::bytes("512").unwrap(), 512);
+    assert_eq!(units::bytes("512B").unwrap(), 512);
+    assert_eq!(units::bytes("512KiB").unwrap(), 512 * 1024);
+    assert_eq!(units::bytes("10MB").unwrap(), 10_000_000);
+    assert_eq!(units::bytes("10 mb").unwrap(), 10_000_000);
+    assert_eq!(units::bytes("1.5GiB").unwrap(), 1_610_612_736);
+    assert_eq!("2TB".parse::<ByteSize>().unwrap().as_u64(), 2_000_000_000_000);
+}
+
+#[test]
+fn parse_invalid_byte_sizes() {
+    assert_eq!(units::bytes("").unwrap_err().to_string(), "invalid byte size ``");
+    assert_eq!(units::bytes("MB").unwrap_err().to_string(), "invalid byte size `MB`");
+    assert_eq!(
+        units::bytes("10XB").unwrap_err().to_string(),
+        "unknown unit `xb` in byte size `10XB`"
+    );
+    assert_eq!(
+        units::bytes("1MB 2KB").unwrap_err().to_string(),
+        "invalid byte size `1MB 2KB`"
+    );
+    assert_eq!(
+        units::bytes("20000PB").unwrap_err().to_string(),
+        "invalid byte size `20000PB`"
+    );
+}
+
+#[test]
+fn deserialize_durations_and_byte_sizes() {
+    let config: Limits = desenv::load_from_iter([
+        ("TIMEOUT", "2s"),
+        ("RETRIES", "100ms, 1s"),
+        ("PER_ROUTE", "health=50ms"),
+        ("BUFFER", "64KiB"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.timeout, Duration::from_secs(2));
+    assert_eq!(config.idle, Duration::from_secs(5400));
+    assert_eq!(config.grace, None);
+    assert_eq!(config.retries, vec![Duration::from_millis(100), Duration::from_secs(1)]);
+    assert_eq!(config.per_route.get("health"), Some(&Duration::from_millis(50)));
+    assert_eq!(config.buffer, ByteSize(65536));
+    assert_eq!(u64::from(config.max_body), 10_000_000);
+}
+
+#[test]
+fn deserialize_invalid_duration_fail() {
+    let config: Result<Limits, Error> = desenv::load_from_iter([
+        ("TIMEOUT", "2 parsecs"),
+        ("RETRIES", ""),
+        ("PER_ROUTE", ""),
+        ("BUFFER", "1"),
+    ]);
+
+    assert_eq!(
+        config.unwrap_err().to_string(),
+        "Cannot parse env var `TIMEOUT` into `Limits.timeout`: unknown unit `parsecs` in duration `2 parsecs`"
+    );
+}
+
+mod ticks {
+    use std::num::ParseIntError;
+    use std::str::FromStr;
+
+    use desenv::Desenv;
+
+    #[derive(Debug, PartialEq)]
+    pub struct Duration(pub u64);
+
+    impl FromStr for Duration {
+        type Err = ParseIntError;
+
+        fn from_str(value: &str) -> Result<Self, Self::Err> {
+            value.parse().map(Self)
+        }
+    }
+
+    #[derive(Desenv, Debug)]
+    pub struct Idle {
+        // A bare `Duration` is taken for the standard one, unless the parser is set.
+        #[desenv(parse_with = "str::parse")]
+        pub idle: Duration,
+    }
+}
+
+#[derive(Desenv, Debug)]
+pub struct Ticks {
+    pub timeout: ticks::Duration,
+    pub grace: Option<ticks::Duration>,
+    #[desenv(nested)]
+    pub idle: ticks::Idle,
+    pub std: core::time::Duration,
+}
+
+#[test]
+fn deserialize_other_duration_types_with_from_str() {
+    let config: Ticks =
+        desenv::load_from_iter([("TIMEOUT", "20"), ("GRACE", "5"), ("IDLE", "100"), ("STD", "20s")]).unwrap();
+
+    assert_eq!(config.timeout, ticks::Duration(20));
+    assert_eq!(config.grace, Some(ticks::Duration(5)));
+    assert_eq!(config.idle.idle, ticks::Duration(100));
+    assert_eq!(config.std, Duration::from_secs(20));
+}
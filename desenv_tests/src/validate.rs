@@ -0,0 +1,100 @@
+use desenv::{Desenv, Error};
+
+#[derive(Desenv, Debug)]
+pub struct Server {
+    #[desenv(validate(range(min = 1, max = 65535)))]
+    pub port: u32,
+    #[desenv(validate(non_empty, len(max = 8)))]
+    pub name: String,
+    #[desenv(validate(one_of("debug", "info", "warn")))]
+    pub level: Option<String>,
+    #[desenv(validate(regex = "^https?://"))]
+    pub url: String,
+    #[desenv(validate(range(min = 0.0, max = 1.0)))]
+    pub ratio: f64,
+    #[desenv(validate(len(min = 1)), default)]
+    pub hosts: Vec<String>,
+    #[desenv(validate_with = "even")]
+    pub workers: u8,
+}
+
+// `validate_with` functions take the value by reference.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn even(value: &u8) -> Result<(), String> {
+    if value % 2 == 0 {
+        Ok(())
+    } else {
+        Err(format!("{value} workers are not even"))
+    }
+}
+
+#[test]
+fn validate_fields() {
+    let config: Server = desenv::load_from_iter([
+        ("PORT", "8080"),
+        ("NAME", "api"),
+        ("URL", "https://example.com"),
+        ("RATIO", "0.5"),
+        ("HOSTS", "a"),
+        ("WORKERS", "4"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.name, "api");
+    assert_eq!(config.level, None);
+    assert_eq!(config.url, "https://example.com");
+    assert!((config.ratio - 0.5).abs() < f64::EPSILON);
+    assert_eq!(config.hosts, vec!["a".to_string()]);
+    assert_eq!(config.workers, 4);
+}
+
+#[test]
+fn validate_fields_fail_naming_variable_and_rule() {
+    let config: Result<Server, Error> = desenv::load_from_iter([
+        ("PORT", "0"),
+        ("NAME", ""),
+        ("LEVEL", "trace"),
+        ("URL", "ftp://example.com"),
+        ("RATIO", "1.5"),
+        ("WORKERS", "3"),
+    ]);
+
+    let errors: Vec<String> = match config.unwrap_err() {
+        Error::Multiple(errors) => errors.iter().map(ToString::to_string).collect(),
+        error => panic!("expected multiple errors, got {error}"),
+    };
+    assert_eq!(
+        errors,
+        vec![
+            "Invalid env var `PORT` for `Server.port`: must be between 1 and 65535",
+            "Invalid env var `NAME` for `Server.name`: must not be empty",
+            "Invalid env var `LEVEL` for `Server.level`: must be one of `debug`, `info`, `warn`",
+            "Invalid env var `URL` for `Server.url`: must match `^https?://`",
+            "Invalid env var `RATIO` for `Server.ratio`: must be between 0 and 1",
+            "Invalid env var `HOSTS` for `Server.hosts`: length must be at least 1",
+            "Invalid env var `WORKERS` for `Server.workers`: 3 workers are not even",
+        ]
+    );
+}
+
+#[test]
+fn validate_fields_fail_with_validation_error() {
+    let config: Result<Server, Error> = desenv::load_from_iter([
+        ("PORT", "80"),
+        ("NAME", "too-long-name"),
+        ("URL", "http://example.com"),
+        ("RATIO", "1"),
+        ("HOSTS", "a"),
+        ("WORKERS", "2"),
+    ]);
+
+    match config.unwrap_err() {
+        Error::Validation(error) => {
+            assert_eq!(error.var(), "NAME");
+            assert_eq!(error.path(), "Server.name");
+            assert_eq!(error.rule(), "length must be at most 8");
+        }
+        error => panic!("expected validation error, got {error}"),
+    }
+}
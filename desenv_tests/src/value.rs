@@ -0,0 +1,62 @@
+use desenv::{Desenv, DesenvValue, Error, VariantError};
+
+#[derive(DesenvValue, Debug, Eq, PartialEq)]
+pub enum Plain {
+    Val1,
+    Val2,
+}
+
+#[derive(DesenvValue, Debug, Eq, PartialEq)]
+#[desenv(rename_all = "kebab-case")]
+pub enum LogLevel {
+    #[desenv(alias = "dbg", alias = "trace")]
+    Debug,
+    InfoOnly,
+    #[desenv(rename = "WARN")]
+    Warning,
+}
+
+#[derive(Desenv, Debug)]
+pub struct Config {
+    pub level: LogLevel,
+    pub levels: Vec<LogLevel>,
+    pub plain: Option<Plain>,
+}
+
+#[test]
+fn parse_variant_names() {
+    assert_eq!("Val1".parse::<Plain>().unwrap(), Plain::Val1);
+    assert_eq!("info-only".parse::<LogLevel>().unwrap(), LogLevel::InfoOnly);
+    assert_eq!("WARN".parse::<LogLevel>().unwrap(), LogLevel::Warning);
+    assert_eq!("trace".parse::<LogLevel>().unwrap(), LogLevel::Debug);
+}
+
+#[test]
+fn fail_to_parse_unknown_variant() {
+    let error: VariantError = "warning".parse::<LogLevel>().unwrap_err();
+    assert_eq!(error.value(), "warning");
+    assert_eq!(error.expected(), ["debug", "dbg", "trace", "info-only", "WARN"]);
+    assert_eq!(
+        error.to_string(),
+        "unknown variant `warning`, expected one of `debug`, `dbg`, `trace`, `info-only`, `WARN`"
+    );
+}
+
+#[test]
+fn deserialize_enum_fields() {
+    let config: Config =
+        desenv::load_from_iter([("LEVEL", "dbg"), ("LEVELS", "WARN,info-only"), ("PLAIN", "Val2")]).unwrap();
+    assert_eq!(config.level, LogLevel::Debug);
+    assert_eq!(config.levels, vec![LogLevel::Warning, LogLevel::InfoOnly]);
+    assert_eq!(config.plain, Some(Plain::Val2));
+}
+
+#[test]
+fn deserialize_enum_field_fail() {
+    let config: Result<Config, Error> = desenv::load_from_iter([("LEVEL", "info"), ("LEVELS", "")]);
+    assert_eq!(
+        config.unwrap_err().to_string(),
+        "Cannot parse env var `LEVEL` into `Config.level`: unknown variant `info`, expected one of `debug`, `dbg`, \
+         `trace`, `info-only`, `WARN`"
+    );
+}
@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+#[desenv(tag = "KIND")]
+enum Storage {
+    Local(String),
+}
+
+fn main(){}
@@ -0,0 +1,8 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+enum Storage {
+    Local { path: String },
+}
+
+fn main(){}
@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+#[desenv(tag = 1)]
+enum Storage {
+    Local { path: String },
+}
+
+fn main(){}
@@ -0,0 +1,12 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+#[desenv(tag = "KIND", prefix = "STORAGE_", rename_all = "snake_case")]
+enum Storage {
+    #[desenv(prefix = "S3_")]
+    S3 { bucket: String },
+    Local { path: String },
+    Memory,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use std::collections::HashMap;
+
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(alias = "OLD_MAP")]
+    map: HashMap<String, String>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(deprecated_alias)]
+    field: String,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(conflicts_with = "nested")]
+    field: Option<String>,
+    #[desenv(nested)]
+    nested: Nested,
+}
+
+#[derive(Desenv)]
+struct Nested {
+    field: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(requires = "missing")]
+    field: Option<String>,
+}
+
+fn main() {}
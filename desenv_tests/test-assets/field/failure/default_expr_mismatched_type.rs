@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(default_expr = "\"8080\"")]
+    field: u16,
+}
+
+fn main() {}
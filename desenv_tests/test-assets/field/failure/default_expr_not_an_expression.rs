@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(default_expr = "not a number")]
+    field: u16,
+}
+
+fn main() {}
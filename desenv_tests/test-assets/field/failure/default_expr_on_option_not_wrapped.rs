@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(default_expr = 5)]
+    field: Option<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use desenv::Desenv;
+
+fn default_field() -> String {
+    "value".to_string()
+}
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(default = "value", default_fn = "default_field")]
+    field: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(kind = "list")]
+    field: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(kind)]
+    field: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use std::collections::HashMap;
+
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(default = "a=1")]
+    field: HashMap<String, String>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(map(inline))]
+    field: Vec<String>,
+}
+
+fn main() {}
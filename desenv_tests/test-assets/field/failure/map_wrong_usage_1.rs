@@ -0,0 +1,11 @@
+use std::collections::HashMap;
+
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(map)]
+    field: HashMap<String, String>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(nested(prefix = ""))]
+    field: String
+}
+
+fn main(){}
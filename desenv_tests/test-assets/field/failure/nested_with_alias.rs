@@ -0,0 +1,14 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(nested, alias = "OLD_")]
+    field: Nested,
+}
+
+#[derive(Desenv)]
+struct Nested {
+    field: String,
+}
+
+fn main() {}
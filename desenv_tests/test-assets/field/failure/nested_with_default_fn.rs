@@ -0,0 +1,18 @@
+use desenv::Desenv;
+
+fn default_nested() -> Nested {
+    Nested { field: "value".to_string() }
+}
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(nested, default_fn = "default_nested")]
+    field: Nested,
+}
+
+#[derive(Desenv)]
+struct Nested {
+    field: String,
+}
+
+fn main() {}
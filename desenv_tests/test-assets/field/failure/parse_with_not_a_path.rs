@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(parse_with = "not a path")]
+    field: String,
+}
+
+fn main() {}
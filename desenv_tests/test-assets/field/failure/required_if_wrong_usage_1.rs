@@ -0,0 +1,10 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(required_if(field = "other"))]
+    field: Option<String>,
+    other: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(validate(regex = "^[a-z"))]
+    field: String,
+}
+
+fn main() {}
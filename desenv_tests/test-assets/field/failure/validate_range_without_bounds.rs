@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(validate(range()))]
+    field: u8,
+}
+
+fn main() {}
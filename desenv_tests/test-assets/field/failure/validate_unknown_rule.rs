@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(validate(positive))]
+    field: u8,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use desenv::Desenv;
+
+#[derive(Desenv)]
+#[desenv(file)]
+struct Config {
+    #[desenv(alias = "OLD_FIELD", alias = "OLDER_FIELD", deprecated_alias = "OLDEST_FIELD")]
+    field: String,
+    #[desenv(deprecated_alias = "OLD_PATH")]
+    path: Option<PathBuf>,
+    #[desenv(alias = "OLD_LIST", separator = ';')]
+    list: Vec<u8>,
+    #[desenv(alias = "OLD_MAP", map(inline))]
+    map: HashMap<String, String>,
+    #[desenv(requires = "field", deprecated_alias = "OLD_OTHER")]
+    other: Option<String>,
+}
+
+fn main() {}
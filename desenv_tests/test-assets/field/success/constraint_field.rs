@@ -0,0 +1,16 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+#[desenv(file)]
+struct Config {
+    #[desenv(requires = "password", conflicts_with = "token")]
+    user: Option<String>,
+    password: Option<String>,
+    #[desenv(rename = "API_TOKEN")]
+    token: Option<String>,
+    mode: String,
+    #[desenv(required_if(field = "mode", eq = "remote"), required_if(field = "mode", eq = "cluster"))]
+    url: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+use desenv::Desenv;
+
+fn default_name() -> String {
+    "name".to_string()
+}
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(default_fn = "default_name")]
+    name: String,
+    #[desenv(default_expr = 8080)]
+    port: u16,
+    #[desenv(default_expr = "Duration::from_millis(500)")]
+    timeout: Duration,
+    #[desenv(default_expr = "vec![1, 2]", separator = ';')]
+    list: Vec<u8>,
+    #[desenv(default_expr = "None")]
+    other: Option<bool>,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use std::path::PathBuf;
+
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(default(env = "ENV_1", env = "ENV_2"))]
+    field: String,
+    #[desenv(default(env = "ENV_1", env = "ENV_2", value = "1"))]
+    number: u8,
+    #[desenv(default(env = "ENV_1", value = "a,b"))]
+    list: Vec<String>,
+    #[desenv(default(env = "ENV_1", env = "ENV_2", value = "/tmp"))]
+    path: PathBuf,
+    #[desenv(default(env = "ENV_1", value = "true"))]
+    other: Option<bool>,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use desenv::Desenv;
+
+type List = Vec<String>;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(kind = "vec", separator = ';')]
+    field: List,
+    #[desenv(kind = "plain")]
+    other: String,
+}
+
+fn main() {}
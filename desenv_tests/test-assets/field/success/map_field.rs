@@ -0,0 +1,13 @@
+use std::collections::{BTreeMap, HashMap};
+
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(map(key_case = "snake_case"))]
+    scanned: HashMap<String, String>,
+    #[desenv(map(inline, separator = ';', key_separator = ':'), default)]
+    inline: BTreeMap<String, u32>,
+}
+
+fn main() {}
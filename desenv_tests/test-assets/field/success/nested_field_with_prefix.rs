@@ -0,0 +1,14 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(nested(prefix = "REPLICA_"))]
+    field: NestedConfig
+}
+
+#[derive(Desenv)]
+struct NestedConfig {
+    nested_field: String,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(nested)]
+    field: Option<Nested>,
+}
+
+#[derive(Desenv)]
+struct Nested {
+    field: String,
+}
+
+fn main() {}
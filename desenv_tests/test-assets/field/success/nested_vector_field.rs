@@ -0,0 +1,16 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(nested)]
+    field: Vec<Nested>,
+    #[desenv(nested(prefix = "OTHER_"))]
+    other: Vec<Nested>,
+}
+
+#[derive(Desenv)]
+struct Nested {
+    field: String,
+}
+
+fn main() {}
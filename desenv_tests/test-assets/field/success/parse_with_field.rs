@@ -0,0 +1,15 @@
+use desenv::Desenv;
+
+struct Port(u16);
+
+fn parse_port(value: &str) -> Result<Port, std::num::ParseIntError> {
+    value.parse().map(Port)
+}
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(parse_with = "parse_port")]
+    field: Port,
+}
+
+fn main() {}
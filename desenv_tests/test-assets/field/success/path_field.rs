@@ -0,0 +1,13 @@
+use std::path::PathBuf;
+
+use desenv::Desenv;
+
+#[derive(Desenv)]
+struct Config {
+    field: PathBuf,
+    optional: Option<PathBuf>,
+    #[desenv(separator = ';', default)]
+    paths: Vec<PathBuf>,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use desenv::{Desenv, Secret};
+
+#[derive(Desenv)]
+struct Config {
+    field: Secret<String>,
+    #[desenv(secret)]
+    other: String,
+}
+
+fn main() {}
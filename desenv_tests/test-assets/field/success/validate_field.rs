@@ -0,0 +1,21 @@
+use desenv::Desenv;
+
+fn positive(value: &i64) -> Result<(), &'static str> {
+    if *value > 0 {
+        Ok(())
+    } else {
+        Err("must be positive")
+    }
+}
+
+#[derive(Desenv)]
+struct Config {
+    #[desenv(validate(range(min = 1), one_of(1, 2, 3)))]
+    field: u8,
+    #[desenv(validate(non_empty, len(min = 1, max = 3)))]
+    list: Vec<String>,
+    #[desenv(validate_with = "positive")]
+    other: Option<i64>,
+}
+
+fn main() {}
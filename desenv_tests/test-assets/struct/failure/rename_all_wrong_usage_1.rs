@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+#[desenv(rename_all)]
+struct Config {
+    field_name: String
+}
+
+fn main(){}
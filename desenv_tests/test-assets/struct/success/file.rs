@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+#[desenv(file)]
+struct Config {
+    field: String
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+#[desenv(rename_all = "SCREAMING-KEBAB-CASE")]
+struct Config {
+    field_name: String
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use desenv::Desenv;
+
+#[derive(Desenv)]
+#[desenv(strict_bool)]
+struct Config {
+    field: bool,
+    #[desenv(strict_bool)]
+    other: Option<bool>,
+}
+
+fn main() {}
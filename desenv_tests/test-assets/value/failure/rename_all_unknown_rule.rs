@@ -0,0 +1,9 @@
+use desenv::DesenvValue;
+
+#[derive(DesenvValue)]
+#[desenv(rename_all = "Title Case")]
+enum Level {
+    Debug,
+}
+
+fn main(){}
@@ -0,0 +1,11 @@
+use desenv::DesenvValue;
+
+#[derive(DesenvValue)]
+#[desenv(rename_all = "lowercase")]
+enum Level {
+    Debug,
+    #[desenv(alias = "debug")]
+    Trace,
+}
+
+fn main(){}
@@ -0,0 +1,9 @@
+use desenv::DesenvValue;
+
+#[derive(DesenvValue)]
+enum Level {
+    Debug,
+    Custom(String),
+}
+
+fn main(){}
@@ -0,0 +1,8 @@
+use desenv::DesenvValue;
+
+#[derive(DesenvValue)]
+struct Level {
+    field: String,
+}
+
+fn main(){}